@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::*;
+use crate::standings::{regular_season_games, Outcome, Record};
+
+/// How the power ranking score weighs its three components. Weights do not
+/// need to add up to one; scores are only compared with each other.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PowerRankingConfig {
+    /// Weight of points scored per game, relative to the league's best.
+    pub points_for: f64,
+    /// Weight of points per game over the last `recent_weeks`, relative to the league's best.
+    pub recent_form: f64,
+    /// Weight of all-play win percentage.
+    pub all_play: f64,
+    pub recent_weeks: usize,
+}
+
+impl Default for PowerRankingConfig {
+    fn default() -> Self {
+        PowerRankingConfig {
+            points_for: 0.4,
+            recent_form: 0.2,
+            all_play: 0.4,
+            recent_weeks: 3,
+        }
+    }
+}
+
+/// Season analytics for one roster. Only regular season head-to-head weeks count.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RosterAnalytics {
+    pub roster_id: u8,
+    /// Record had the roster played every other roster every week.
+    pub all_play: Record,
+    /// Wins an average schedule would have produced: the sum of each week's all-play win share.
+    pub expected_wins: f64,
+    /// Head-to-head wins, counting ties as half.
+    pub actual_wins: f64,
+    /// `actual_wins - expected_wins`. Positive means a lucky schedule.
+    pub luck: f64,
+    pub points_for: f64,
+    pub points_per_game: f64,
+    pub recent_points_per_game: f64,
+    pub power_score: f64,
+    /// 1-based rank by `power_score`.
+    pub power_rank: u8,
+}
+
+/// Each roster's all-play record over the regular season.
+pub fn all_play_records(
+    league: &League,
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+) -> BTreeMap<u8, Record> {
+    let mut records: BTreeMap<u8, Record> = BTreeMap::new();
+    for scores in weekly_scores(league, matchups).values() {
+        for (roster_id, points) in scores {
+            let record = records.entry(*roster_id).or_default();
+            for (other_id, other_points) in scores {
+                if other_id != roster_id {
+                    record.add(Outcome::from_points(*points, *other_points));
+                }
+            }
+        }
+    }
+
+    records
+}
+
+/// All-play records, expected wins, luck and power rankings for every roster
+/// which played a regular season game, ordered by power rank.
+pub fn season_analytics(
+    league: &League,
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+    config: &PowerRankingConfig,
+) -> Vec<RosterAnalytics> {
+    let weekly = weekly_scores(league, matchups);
+    let all_play = all_play_records(league, matchups);
+
+    let mut actual_wins: BTreeMap<u8, f64> = BTreeMap::new();
+    for game in regular_season_games(league, matchups) {
+        if game.opponent.is_some() {
+            *actual_wins.entry(game.roster_id).or_default() += match game.outcome {
+                Outcome::Win => 1.0,
+                Outcome::Tie => 0.5,
+                Outcome::Loss => 0.0,
+            };
+        }
+    }
+
+    let mut rows: Vec<RosterAnalytics> = all_play
+        .iter()
+        .map(|(&roster_id, record)| {
+            let scores: Vec<f64> = weekly
+                .values()
+                .filter_map(|week| week.get(&roster_id).copied())
+                .collect();
+
+            let expected_wins: f64 = weekly
+                .values()
+                .filter_map(|week| {
+                    let points = week.get(&roster_id)?;
+                    let mut week_record = Record::default();
+                    for (other_id, other_points) in week {
+                        if *other_id != roster_id {
+                            week_record.add(Outcome::from_points(*points, *other_points));
+                        }
+                    }
+                    Some(week_record.win_pct())
+                })
+                .sum();
+
+            let actual = actual_wins.get(&roster_id).copied().unwrap_or(0.0);
+            let points_for: f64 = scores.iter().sum();
+            let recent = &scores[scores.len().saturating_sub(config.recent_weeks)..];
+
+            RosterAnalytics {
+                roster_id,
+                all_play: *record,
+                expected_wins: round(expected_wins),
+                actual_wins: actual,
+                luck: round(actual - expected_wins),
+                points_for: round(points_for),
+                points_per_game: round(mean(&scores)),
+                recent_points_per_game: round(mean(recent)),
+                power_score: 0.0,
+                power_rank: 0,
+            }
+        })
+        .collect();
+
+    let best = |f: fn(&RosterAnalytics) -> f64| rows.iter().map(f).fold(0.0, f64::max);
+    let best_ppg = best(|r| r.points_per_game);
+    let best_recent = best(|r| r.recent_points_per_game);
+
+    for row in rows.iter_mut() {
+        let score = config.points_for * ratio(row.points_per_game, best_ppg)
+            + config.recent_form * ratio(row.recent_points_per_game, best_recent)
+            + config.all_play * row.all_play.win_pct();
+        row.power_score = (score * 1000.0).round() / 1000.0;
+    }
+
+    rows.sort_by(|a, b| {
+        b.power_score
+            .total_cmp(&a.power_score)
+            .then_with(|| a.roster_id.cmp(&b.roster_id))
+    });
+    for (i, row) in rows.iter_mut().enumerate() {
+        row.power_rank = i as u8 + 1;
+    }
+
+    rows
+}
+
+/// Week => roster => points, for played regular season head-to-head weeks.
+fn weekly_scores(
+    league: &League,
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+) -> BTreeMap<u8, BTreeMap<u8, f64>> {
+    let mut weekly: BTreeMap<u8, BTreeMap<u8, f64>> = BTreeMap::new();
+    for game in regular_season_games(league, matchups) {
+        if game.opponent.is_some() {
+            weekly
+                .entry(game.week)
+                .or_default()
+                .insert(game.roster_id, game.points_for);
+        }
+    }
+
+    weekly
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+fn ratio(value: f64, best: f64) -> f64 {
+    if best > 0.0 {
+        value / best
+    } else {
+        0.0
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{league, season};
+
+    #[test]
+    fn test_all_play_records() {
+        let records = all_play_records(&league(15, 0), &season());
+
+        // Week scores: 1: 100 90 120 80, 2: 110 95 100 105, 3: 90 115 100 130
+        assert_eq!(records[&1].to_string(), "5-4-0");
+        assert_eq!(records[&2].to_string(), "3-6-0");
+        assert_eq!(records[&3].to_string(), "5-4-0");
+        assert_eq!(records[&4].to_string(), "5-4-0");
+    }
+
+    #[test]
+    fn test_expected_wins_and_luck() {
+        let rows = season_analytics(&league(15, 0), &season(), &PowerRankingConfig::default());
+        let row = |id: u8| rows.iter().find(|r| r.roster_id == id).unwrap();
+
+        assert_eq!(row(2).expected_wins, 1.0);
+        assert_eq!(row(2).actual_wins, 1.0);
+        assert_eq!(row(2).luck, 0.0);
+
+        assert_eq!(row(3).expected_wins, 1.67);
+        assert_eq!(row(3).actual_wins, 1.0);
+        assert_eq!(row(3).luck, -0.67);
+
+        assert_eq!(row(1).luck, 0.33);
+        let total_luck: f64 = rows.iter().map(|r| r.luck).sum();
+        assert!(total_luck.abs() < 0.02);
+    }
+
+    #[test]
+    fn test_power_rankings() {
+        let rows = season_analytics(&league(15, 0), &season(), &PowerRankingConfig::default());
+
+        let order: Vec<u8> = rows.iter().map(|r| r.roster_id).collect();
+        assert_eq!(order, vec![3, 4, 1, 2]);
+        assert_eq!(rows[0].power_rank, 1);
+        assert_eq!(rows[0].recent_points_per_game, 106.67);
+
+        let recent_only = PowerRankingConfig {
+            points_for: 0.0,
+            recent_form: 1.0,
+            all_play: 0.0,
+            recent_weeks: 1,
+        };
+        let rows = season_analytics(&league(15, 0), &season(), &recent_only);
+        assert_eq!(rows[0].roster_id, 4);
+        assert_eq!(rows[0].power_score, 1.0);
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::json;
+
+use sleeper::client::Client;
+use sleeper::data::*;
+use sleeper::search::{PlayerIndex, PlayerQuery};
+use sleeper::snapshot::LeagueSnapshot;
+use sleeper::standings::{is_regular_season_week, standings};
+
+use crate::output::Output;
+
+pub async fn league(client: &Client, league_id: &str) -> Result<Output, SleeperError> {
+    let league = client.get_league(league_id).await?;
+    let positions: Vec<String> = league
+        .roster_positions
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+
+    let rows = vec![
+        vec!["name".to_string(), league.name.clone()],
+        vec!["league_id".to_string(), league.league_id.clone()],
+        vec!["season".to_string(), league.season.to_string()],
+        vec!["status".to_string(), league.status.clone()],
+        vec!["rosters".to_string(), league.total_rosters.to_string()],
+        vec![
+            "playoff_teams".to_string(),
+            league.settings.playoff_teams.to_string(),
+        ],
+        vec![
+            "playoff_week_start".to_string(),
+            league.settings.playoff_week_start.to_string(),
+        ],
+        vec!["roster_positions".to_string(), positions.join(" ")],
+        vec![
+            "previous_league_id".to_string(),
+            league.previous_league().cloned().unwrap_or_default(),
+        ],
+    ];
+
+    Ok(Output::new(&league, vec!["field", "value"], rows))
+}
+
+pub async fn rosters(client: &Client, league_id: &str) -> Result<Output, SleeperError> {
+    let snapshot = client.get_league_snapshot(league_id, []).await?;
+
+    let mut json = Vec::new();
+    let mut rows = Vec::new();
+    for team in snapshot.teams() {
+        let settings = &team.roster.settings;
+        let record = format!("{}-{}-{}", settings.wins, settings.losses, settings.ties);
+        json.push(json!({
+            "roster_id": team.roster.roster_id,
+            "team": team.name(),
+            "owner_id": team.roster.owner_id,
+            "record": record,
+            "points_for": settings.points_for(),
+            "players": team.roster.players,
+        }));
+        rows.push(vec![
+            team.roster.roster_id.to_string(),
+            team.name().unwrap_or_default().to_string(),
+            record,
+            format!("{:.2}", settings.points_for()),
+            team.roster.players.len().to_string(),
+        ]);
+    }
+
+    Ok(Output::new(
+        &json,
+        vec!["roster_id", "team", "record", "points_for", "players"],
+        rows,
+    ))
+}
+
+pub async fn matchups(client: &Client, league_id: &str, week: u8) -> Result<Output, SleeperError> {
+    let snapshot = client.get_league_snapshot(league_id, [week]).await?;
+
+    let mut matchups: Vec<&Matchup> = snapshot.matchups(week).iter().collect();
+    matchups.sort_by_key(|m| (m.matchup_id.is_none(), m.matchup_id, m.roster_id));
+    let rows = matchups
+        .iter()
+        .map(|m| {
+            vec![
+                m.matchup_id.map(|id| id.to_string()).unwrap_or_default(),
+                m.roster_id.to_string(),
+                team_name(&snapshot, m.roster_id),
+                format!("{:.2}", m.total_points()),
+            ]
+        })
+        .collect();
+
+    Ok(Output::new(
+        &matchups,
+        vec!["matchup_id", "roster_id", "team", "points"],
+        rows,
+    ))
+}
+
+pub async fn league_standings(client: &Client, league_id: &str) -> Result<Output, SleeperError> {
+    let league = client.get_league(league_id).await?;
+    let last_week = match league.settings.playoff_week_start {
+        0 => 18,
+        week => week - 1,
+    };
+    let weeks: Vec<u8> = (league.settings.start_week..=last_week)
+        .filter(|week| is_regular_season_week(&league, *week))
+        .collect();
+    let snapshot = client.get_league_snapshot(league_id, weeks).await?;
+
+    let table = standings(&snapshot.league, &snapshot.rosters, &snapshot.matchups);
+    let rows = table
+        .iter()
+        .map(|row| {
+            vec![
+                row.rank.to_string(),
+                team_name(&snapshot, row.roster_id),
+                row.record.to_string(),
+                format!("{:.2}", row.points_for),
+                format!("{:.2}", row.points_against),
+                row.streak.map(|s| s.to_string()).unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    Ok(Output::new(
+        &table,
+        vec![
+            "rank",
+            "team",
+            "record",
+            "points_for",
+            "points_against",
+            "streak",
+        ],
+        rows,
+    ))
+}
+
+pub async fn search_players(
+    client: &Client,
+    name: &str,
+    limit: usize,
+) -> Result<Output, SleeperError> {
+    let players = client.get_all_players(SleeperSport::NFL).await?;
+    let index = PlayerIndex::new(&players);
+    let matches = index.search(&PlayerQuery::name(name).limit(limit));
+
+    let found: Vec<&NflPlayer> = matches.iter().map(|m| m.player).collect();
+    let rows = found
+        .iter()
+        .map(|p| {
+            vec![
+                p.player_id.clone(),
+                p.full_name.clone().unwrap_or_default(),
+                p.position
+                    .as_ref()
+                    .map(|p| p.to_string())
+                    .unwrap_or_default(),
+                p.team.as_ref().map(|t| t.to_string()).unwrap_or_default(),
+                p.injury_status.code().unwrap_or_default().to_string(),
+            ]
+        })
+        .collect();
+
+    Ok(Output::new(
+        &found,
+        vec!["player_id", "name", "position", "team", "injury"],
+        rows,
+    ))
+}
+
+pub async fn transactions(
+    client: &Client,
+    league_id: &str,
+    week: u8,
+) -> Result<Output, SleeperError> {
+    let (snapshot, mut transactions) = futures::try_join!(
+        client.get_league_snapshot(league_id, []),
+        client.get_transactions(league_id, week),
+    )?;
+    transactions.sort_by_key(|t| t.created);
+
+    let rows = transactions
+        .iter()
+        .map(|t| {
+            vec![
+                t.transaction_id.clone(),
+                t.transaction_type.to_string(),
+                t.status.to_string(),
+                t.roster_ids
+                    .iter()
+                    .map(|r| team_name(&snapshot, *r))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                moves(&snapshot, &t.adds),
+                moves(&snapshot, &t.drops),
+            ]
+        })
+        .collect();
+
+    Ok(Output::new(
+        &transactions,
+        vec!["transaction_id", "type", "status", "teams", "adds", "drops"],
+        rows,
+    ))
+}
+
+pub async fn draft(client: &Client, league_id: &str) -> Result<Output, SleeperError> {
+    let (snapshot, drafts) = futures::try_join!(
+        client.get_league_snapshot(league_id, []),
+        client.get_league_drafts(league_id),
+    )?;
+    let Some(draft) = drafts.first() else {
+        return Ok(Output::new(
+            &Vec::<DraftPick>::new(),
+            vec!["pick", "round", "team", "player", "position", "nfl_team"],
+            Vec::new(),
+        ));
+    };
+
+    let picks = client.get_draft_picks(&draft.draft_id).await?;
+    let rows = picks
+        .iter()
+        .map(|pick| {
+            let roster_id = pick
+                .roster_id
+                .or_else(|| draft.roster_for_slot(pick.draft_slot));
+            vec![
+                pick.pick_no.to_string(),
+                pick.round.to_string(),
+                roster_id
+                    .map(|id| team_name(&snapshot, id))
+                    .unwrap_or_default(),
+                pick.player_name().unwrap_or_else(|| pick.player_id.clone()),
+                pick.position().map(|p| p.to_string()).unwrap_or_default(),
+                pick.team().map(|t| t.to_string()).unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    Ok(Output::new(
+        &picks,
+        vec!["pick", "round", "team", "player", "position", "nfl_team"],
+        rows,
+    ))
+}
+
+pub async fn user_leagues(
+    client: &Client,
+    username: &str,
+    season: Option<u16>,
+) -> Result<Output, SleeperError> {
+    let season = match season {
+        Some(season) => Season(season),
+        None => {
+            client
+                .get_sport_state(SleeperSport::NFL)
+                .await?
+                .league_season
+        }
+    };
+    let user = client.get_user(username).await?;
+    let leagues = client
+        .get_user_leagues(&user.user_id, SleeperSport::NFL, season)
+        .await?;
+
+    let rows = leagues
+        .iter()
+        .map(|l| {
+            vec![
+                l.league_id.clone(),
+                l.name.clone(),
+                l.season.to_string(),
+                l.status.clone(),
+                l.total_rosters.to_string(),
+            ]
+        })
+        .collect();
+
+    Ok(Output::new(
+        &leagues,
+        vec!["league_id", "name", "season", "status", "rosters"],
+        rows,
+    ))
+}
+
+fn team_name(snapshot: &LeagueSnapshot, roster_id: u8) -> String {
+    snapshot
+        .team_name(roster_id)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("Roster {}", roster_id))
+}
+
+/// Players moved per roster, e.g. "Gridiron: 4046; Roster 2: 6794 7564".
+fn moves(snapshot: &LeagueSnapshot, players: &Option<HashMap<PlayerId, u8>>) -> String {
+    let by_roster: BTreeMap<u8, Vec<&PlayerId>> =
+        players
+            .iter()
+            .flatten()
+            .fold(BTreeMap::new(), |mut acc, (player, roster)| {
+                acc.entry(*roster).or_insert_with(Vec::new).push(player);
+                acc
+            });
+    by_roster
+        .iter()
+        .map(|(roster, players)| {
+            let mut players = players.clone();
+            players.sort();
+            format!(
+                "{}: {}",
+                team_name(snapshot, *roster),
+                players
+                    .iter()
+                    .map(|p| p.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Format;
+
+    #[tokio::test]
+    async fn test_transactions() {
+        let mut server = mockito::Server::new_async().await;
+        let mut league: serde_json::Value =
+            serde_json::from_str(include_str!("../../../tests/fixtures/league.json")).unwrap();
+        league["league_id"] = "1".into();
+
+        let mocks = [
+            ("/league/1", league.to_string()),
+            (
+                "/league/1/users",
+                r#"[{"user_id": "u1", "username": "alice", "settings": null,
+                    "metadata": {"team_name": "Gridiron"}, "is_owner": true, "is_bot": false,
+                    "league_id": "1", "display_name": "Alice", "avatar": null}]"#
+                    .to_string(),
+            ),
+            (
+                "/league/1/rosters",
+                r#"[{"taxi": null, "starters": [], "settings": {}, "roster_id": 1,
+                    "reserve": null, "players": [], "player_map": null, "owner_id": "u1",
+                    "metadata": null, "league_id": "1", "keepers": null, "co_owners": null}]"#
+                    .to_string(),
+            ),
+            (
+                "/league/1/transactions/3",
+                include_str!("../../../tests/fixtures/transactions_nfl_week_3.json").to_string(),
+            ),
+        ];
+        for (path, body) in mocks {
+            server
+                .mock("GET", path)
+                .with_body(body)
+                .create_async()
+                .await;
+        }
+
+        let client = Client::with_base_url(&server.url());
+        let output = transactions(&client, "1", 3).await.unwrap();
+
+        let mut csv = Vec::new();
+        output.write(&mut csv, Format::Csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "transaction_id,type,status,teams,adds,drops");
+        assert_eq!(lines.len(), 6);
+        assert!(lines.contains(
+            &"1010000000000000001,trade,complete,\"Gridiron, Roster 2\",Gridiron: 4046; Roster 2: 6794 7564,Gridiron: 6794 7564; Roster 2: 4046"
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+use sleeper::client::Client;
+use sleeper::data::*;
+
+mod commands;
+mod output;
+
+use commands::*;
+use output::{Format, Output};
+
+/// Everyday queries against the Sleeper fantasy API.
+#[derive(Parser)]
+#[command(name = "sleeper", version)]
+struct Cli {
+    /// How to print results.
+    #[arg(long, short, value_enum, default_value_t = Format::Table, global = true)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// A league's settings.
+    League { league_id: String },
+    /// Every roster in a league with its owner and record.
+    Rosters { league_id: String },
+    /// Scores for one week.
+    Matchups {
+        league_id: String,
+        #[arg(long)]
+        week: u8,
+    },
+    /// Regular season standings.
+    Standings { league_id: String },
+    /// Look up NFL players.
+    Players {
+        #[command(subcommand)]
+        command: PlayersCommand,
+    },
+    /// Trades, waiver claims and free agent moves for one week.
+    Transactions {
+        league_id: String,
+        #[arg(long)]
+        week: u8,
+    },
+    /// The picks of a league's most recent draft.
+    Draft { league_id: String },
+    /// Look up users.
+    User {
+        #[command(subcommand)]
+        command: UserCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum PlayersCommand {
+    /// Find players by name, allowing for typos.
+    Search {
+        name: String,
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum UserCommand {
+    /// The NFL leagues a user is in.
+    Leagues {
+        username: String,
+        /// Defaults to the current league season.
+        #[arg(long)]
+        season: Option<u16>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let client = Client::new();
+
+    match run(&client, cli.command)
+        .await
+        .and_then(|o| o.print(cli.format))
+    {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(SleeperError::NetworkError(Some(status))) => {
+            eprintln!("sleeper: request to Sleeper failed with {}", status);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("sleeper: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(client: &Client, command: Command) -> Result<Output, SleeperError> {
+    match command {
+        Command::League { league_id } => league(client, &league_id).await,
+        Command::Rosters { league_id } => rosters(client, &league_id).await,
+        Command::Matchups { league_id, week } => matchups(client, &league_id, week).await,
+        Command::Standings { league_id } => league_standings(client, &league_id).await,
+        Command::Players {
+            command: PlayersCommand::Search { name, limit },
+        } => search_players(client, &name, limit).await,
+        Command::Transactions { league_id, week } => transactions(client, &league_id, week).await,
+        Command::Draft { league_id } => draft(client, &league_id).await,
+        Command::User {
+            command: UserCommand::Leagues { username, season },
+        } => user_leagues(client, &username, season).await,
+    }
+}
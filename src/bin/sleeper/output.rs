@@ -0,0 +1,144 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+use sleeper::data::SleeperError;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+/// What a command produced: the raw data for JSON, and rows for table and CSV.
+pub struct Output {
+    json: Value,
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Output {
+    pub fn new<T: Serialize>(data: &T, headers: Vec<&'static str>, rows: Vec<Vec<String>>) -> Self {
+        Output {
+            json: serde_json::to_value(data).unwrap_or(Value::Null),
+            headers,
+            rows,
+        }
+    }
+
+    pub fn print(&self, format: Format) -> Result<(), SleeperError> {
+        self.write(io::stdout(), format)
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W, format: Format) -> Result<(), SleeperError> {
+        let export_error = |e: io::Error| SleeperError::ExportError(e.to_string());
+
+        match format {
+            Format::Json => {
+                serde_json::to_writer_pretty(&mut writer, &self.json)
+                    .map_err(|e| SleeperError::ExportError(e.to_string()))?;
+                writeln!(writer).map_err(export_error)
+            }
+            Format::Csv => {
+                let mut csv = csv::Writer::from_writer(writer);
+                csv.write_record(&self.headers)
+                    .map_err(|e| SleeperError::ExportError(e.to_string()))?;
+                for row in &self.rows {
+                    csv.write_record(row)
+                        .map_err(|e| SleeperError::ExportError(e.to_string()))?;
+                }
+                csv.flush().map_err(export_error)
+            }
+            Format::Table => {
+                let mut widths: Vec<usize> = self.headers.iter().map(|h| h.len()).collect();
+                for row in &self.rows {
+                    for (width, cell) in widths.iter_mut().zip(row) {
+                        *width = (*width).max(cell.chars().count());
+                    }
+                }
+
+                let line = |cells: &[&str]| {
+                    cells
+                        .iter()
+                        .zip(&widths)
+                        .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                        .collect::<Vec<String>>()
+                        .join("  ")
+                        .trim_end()
+                        .to_string()
+                };
+
+                let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+                writeln!(writer, "{}", line(&self.headers)).map_err(export_error)?;
+                writeln!(
+                    writer,
+                    "{}",
+                    line(&separator.iter().map(String::as_str).collect::<Vec<_>>())
+                )
+                .map_err(export_error)?;
+                for row in &self.rows {
+                    writeln!(
+                        writer,
+                        "{}",
+                        line(&row.iter().map(String::as_str).collect::<Vec<_>>())
+                    )
+                    .map_err(export_error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output() -> Output {
+        Output::new(
+            &serde_json::json!([{ "team": "Gridiron", "points": 101.5 }]),
+            vec!["team", "points"],
+            vec![
+                vec!["Gridiron".to_string(), "101.50".to_string()],
+                vec!["Team Ünïcode, Inc".to_string(), "9.00".to_string()],
+            ],
+        )
+    }
+
+    fn render(output: &Output, format: Format) -> String {
+        let mut out = Vec::new();
+        output.write(&mut out, format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_table_pads_columns_to_the_widest_cell() {
+        assert_eq!(
+            render(&output(), Format::Table),
+            "team               points\n\
+             -----------------  ------\n\
+             Gridiron           101.50\n\
+             Team Ünïcode, Inc  9.00\n"
+        );
+    }
+
+    #[test]
+    fn test_table_without_rows_has_headers() {
+        let empty = Output::new(&Vec::<Value>::new(), vec!["pick", "team"], Vec::new());
+        assert_eq!(render(&empty, Format::Table), "pick  team\n----  ----\n");
+    }
+
+    #[test]
+    fn test_csv_and_json() {
+        assert_eq!(
+            render(&output(), Format::Csv),
+            "team,points\nGridiron,101.50\n\"Team Ünïcode, Inc\",9.00\n"
+        );
+
+        let json: Value = serde_json::from_str(&render(&output(), Format::Json)).unwrap();
+        assert_eq!(json[0]["points"], 101.5);
+    }
+}
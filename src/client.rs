@@ -1,157 +1,281 @@
 use crate::data::*;
-use std::collections::HashMap;
+use crate::history::{LeagueHistory, LeagueSeason};
+use crate::snapshot::LeagueSnapshot;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
+use futures::stream::{self, StreamExt};
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
 const BASE_URL: &str = "https://api.sleeper.app/v1";
+const DEFAULT_CONCURRENCY_LIMIT: usize = 4;
 
 pub struct Client {
     client: reqwest::Client,
+    base_url: String,
+    concurrency_limit: usize,
+}
+
+/// The outcome of fetching several weeks at once. A failed week does not
+/// discard the others; it shows up in `errors` instead.
+#[derive(Debug, Default)]
+pub struct SeasonMatchups {
+    pub matchups: BTreeMap<u8, Vec<Matchup>>,
+    pub errors: BTreeMap<u8, SleeperError>,
+}
+
+impl SeasonMatchups {
+    /// Whether every requested week was fetched.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
 }
 
+#[allow(clippy::new_without_default)]
 impl Client {
+    /// A client for the Sleeper API, which only serves HTTPS. Without one of
+    /// the `default-tls`, `native-tls` or `rustls-tls` features every request
+    /// fails with a `NetworkError`.
     pub fn new() -> Self {
+        Self::build(BASE_URL)
+    }
+
+    /// Use this to point the client at something other than the Sleeper API,
+    /// e.g. a caching proxy or a local server in tests. Plain HTTP is only
+    /// allowed if `base_url` itself is plain HTTP.
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self::build(base_url)
+    }
+
+    fn build(base_url: &str) -> Self {
         Client {
             client: reqwest::Client::builder()
-                .https_only(true)
+                .https_only(!base_url.starts_with("http://"))
                 .timeout(std::time::Duration::new(10, 0))
                 .build()
                 .unwrap(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            concurrency_limit: DEFAULT_CONCURRENCY_LIMIT,
         }
     }
 
-    pub async fn get_league(&self, id: &str) -> Result<League, SleeperError> {
-        let url = format!("{}/league/{}", BASE_URL, &id);
+    /// How many requests methods which fetch in bulk (e.g. `get_season_matchups`)
+    /// may have in flight at once. Defaults to 4; values below 1 are treated as 1.
+    pub fn with_concurrency_limit(mut self, limit: usize) -> Self {
+        self.concurrency_limit = limit.max(1);
+        self
+    }
 
-        let res = match self.client.get(&url).send().await {
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn concurrency_limit(&self) -> usize {
+        self.concurrency_limit
+    }
+
+    async fn get(&self, url: &str) -> Result<reqwest::Response, SleeperError> {
+        let res = match self.client.get(url).send().await {
             Ok(res) => res,
             Err(e) => return Err(SleeperError::NetworkError(e.status())),
         };
 
-        let league: League = match res.json::<League>().await {
-            Ok(league) => league,
-            Err(_) => return Err(SleeperError::DeserializationError(String::from("League"))),
-        };
+        if !res.status().is_success() {
+            return Err(SleeperError::NetworkError(Some(res.status())));
+        }
 
-        Ok(league)
+        Ok(res)
     }
 
-    pub async fn get_rosters(&self, league_id: &str) -> Result<Vec<Roster>, SleeperError> {
-        let url = format!("{}/league/{}/rosters", BASE_URL, &league_id);
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        type_name: &str,
+    ) -> Result<T, SleeperError> {
+        let res = self.get(url).await?;
 
-        let res = match self.client.get(&url).send().await {
-            Ok(res) => res,
-            Err(e) => return Err(SleeperError::NetworkError(e.status())),
-        };
+        match res.json::<T>().await {
+            Ok(t) => Ok(t),
+            Err(_) => Err(SleeperError::DeserializationError(type_name.to_string())),
+        }
+    }
 
-        let rosters: Vec<Roster> = match res.json().await {
-            Ok(rost) => rost,
-            Err(_) => return Err(SleeperError::DeserializationError(String::from("Roster"))),
-        };
+    pub async fn get_league(&self, id: &str) -> Result<League, SleeperError> {
+        let url = format!("{}/league/{}", self.base_url, &id);
+
+        self.get_json(&url, "League").await
+    }
 
-        Ok(rosters)
+    pub async fn get_rosters(&self, league_id: &str) -> Result<Vec<Roster>, SleeperError> {
+        let url = format!("{}/league/{}/rosters", self.base_url, &league_id);
+
+        self.get_json(&url, "Roster").await
     }
 
     pub async fn get_users_in_league(
         &self,
         league_id: &str,
     ) -> Result<Vec<SleeperUser>, SleeperError> {
-        let url = format!("{}/league/{}/users", BASE_URL, &league_id);
+        let url = format!("{}/league/{}/users", self.base_url, &league_id);
 
-        let res = match self.client.get(&url).send().await {
-            Ok(res) => res,
-            Err(e) => return Err(SleeperError::NetworkError(e.status())),
-        };
+        self.get_json(&url, "SleeperUser").await
+    }
 
-        let users: Vec<SleeperUser> = match res.json().await {
-            Ok(users) => users,
-            Err(_) => {
-                return Err(SleeperError::DeserializationError(String::from(
-                    "SleeperUser",
-                )))
+    pub async fn get_matchups(
+        &self,
+        league_id: &str,
+        week: u8,
+    ) -> Result<Vec<Matchup>, SleeperError> {
+        let url = format!("{}/league/{}/matchups/{}", self.base_url, &league_id, week);
+
+        self.get_json(&url, "Matchup").await
+    }
+
+    /// Matchups for every week in `weeks`, fetched concurrently up to the
+    /// client's concurrency limit.
+    pub async fn get_season_matchups(
+        &self,
+        league_id: &str,
+        weeks: impl IntoIterator<Item = u8>,
+    ) -> SeasonMatchups {
+        let results: Vec<(u8, Result<Vec<Matchup>, SleeperError>)> = stream::iter(weeks)
+            .map(|week| async move { (week, self.get_matchups(league_id, week).await) })
+            .buffer_unordered(self.concurrency_limit)
+            .collect()
+            .await;
+
+        let mut season = SeasonMatchups::default();
+        for (week, result) in results {
+            match result {
+                Ok(matchups) => {
+                    season.matchups.insert(week, matchups);
+                }
+                Err(e) => {
+                    season.errors.insert(week, e);
+                }
             }
-        };
+        }
 
-        Ok(users)
+        season
     }
 
-    pub async fn get_matchups(
+    /// Every transaction made during `week`, including failed waiver claims.
+    pub async fn get_transactions(
         &self,
         league_id: &str,
         week: u8,
-    ) -> Result<Vec<Matchup>, SleeperError> {
-        let url = format!("{}/league/{}/matchups/{}", BASE_URL, &league_id, week);
+    ) -> Result<Vec<Transaction>, SleeperError> {
+        let url = format!(
+            "{}/league/{}/transactions/{}",
+            self.base_url, league_id, week
+        );
 
-        let res = match self.client.get(&url).send().await {
-            Ok(res) => res,
-            Err(e) => return Result::Err(SleeperError::NetworkError(e.status())),
-        };
+        self.get_json(&url, "Vec<Transaction>").await
+    }
 
-        let matchups: Vec<Matchup> = match res.json().await {
-            Ok(m) => m,
-            Err(_) => return Err(SleeperError::DeserializationError(String::from("Matchup"))),
-        };
+    /// Look a user up by username or user ID.
+    pub async fn get_user(&self, username_or_id: &str) -> Result<User, SleeperError> {
+        let url = format!("{}/user/{}", self.base_url, username_or_id);
 
-        Ok(matchups)
+        let user: Option<User> = self.get_json(&url, "User").await?;
+        user.ok_or_else(|| SleeperError::UserNotFound(username_or_id.to_string()))
     }
 
-    pub async fn get_sport_state(&self, sport: SleeperSport) -> Result<SportState, SleeperError> {
-        let url = format!("{}/state/{}", BASE_URL, &sport.to_string());
+    /// Every league `user_id` is in for the given sport and season.
+    pub async fn get_user_leagues(
+        &self,
+        user_id: &str,
+        sport: SleeperSport,
+        season: Season,
+    ) -> Result<Vec<League>, SleeperError> {
+        let url = format!(
+            "{}/user/{}/leagues/{}/{}",
+            self.base_url, user_id, sport, season
+        );
 
-        let res = match self.client.get(&url).send().await {
-            Ok(res) => res,
-            Err(e) => return Result::Err(SleeperError::NetworkError(e.status())),
-        };
+        self.get_json(&url, "Vec<League>").await
+    }
 
-        match res.json::<SportState>().await {
-            Ok(st) => Ok(st),
-            Err(_) => Err(SleeperError::DeserializationError(String::from(
-                "SportState",
-            ))),
-        }
+    /// Every draft the league has held, most recent first.
+    pub async fn get_league_drafts(&self, league_id: &str) -> Result<Vec<Draft>, SleeperError> {
+        let url = format!("{}/league/{}/drafts", self.base_url, league_id);
+
+        self.get_json(&url, "Vec<Draft>").await
     }
 
-    // Be careful, it's thicccc
-    pub async fn get_all_players(&self, sport: SleeperSport) -> Result<AllPlayers, SleeperError> {
-        fn to_hashmap_nfl(
-            players_unparsed: &str,
-        ) -> Result<HashMap<PlayerId, NflPlayer>, SleeperError> {
-            let parsed =
-                match serde_json::from_str::<HashMap<PlayerId, NflPlayer>>(players_unparsed) {
-                    Ok(p) => p,
-                    Err(e) => {
-                        panic!("Data model for all players is bad! {}", e);
-                    }
-                };
+    pub async fn get_draft(&self, draft_id: &str) -> Result<Draft, SleeperError> {
+        let url = format!("{}/draft/{}", self.base_url, draft_id);
 
-            Ok(parsed)
-        }
+        self.get_json(&url, "Draft").await
+    }
 
-        let url = format!("{}/players/{}", BASE_URL, &sport.to_string());
+    /// Every pick made so far, in order.
+    pub async fn get_draft_picks(&self, draft_id: &str) -> Result<Vec<DraftPick>, SleeperError> {
+        let url = format!("{}/draft/{}/picks", self.base_url, draft_id);
 
-        let res = match self.client.get(&url).send().await {
-            Ok(res) => res,
-            Err(e) => return Err(SleeperError::NetworkError(e.status())),
+        self.get_json(&url, "Vec<DraftPick>").await
+    }
+
+    /// Every future draft pick which has changed hands in the league.
+    pub async fn get_traded_picks(&self, league_id: &str) -> Result<Vec<TradedPick>, SleeperError> {
+        let url = format!("{}/league/{}/traded_picks", self.base_url, league_id);
+
+        self.get_json(&url, "Vec<TradedPick>").await
+    }
+
+    /// The league, its users, rosters and the matchups of `weeks`, fetched
+    /// concurrently. Fails if any one of them does.
+    ///
+    /// Players are not fetched: the players endpoint is several megabytes and
+    /// Sleeper asks that it be called at most once a day, so fetch it yourself
+    /// and pass it to `LeagueSnapshot::attach_players`. Until then the
+    /// snapshot's `players` is empty.
+    pub async fn get_league_snapshot(
+        &self,
+        league_id: &str,
+        weeks: impl IntoIterator<Item = u8>,
+    ) -> Result<LeagueSnapshot, SleeperError> {
+        let matchups = async {
+            let season = self.get_season_matchups(league_id, weeks).await;
+            match season.errors.into_iter().next() {
+                Some((_, e)) => Err(e),
+                None => Ok(season.matchups),
+            }
         };
 
-        // TODO - revisit type
-        match res.text_with_charset("utf-8").await {
-            Ok(players_raw) => match sport {
-                SleeperSport::NFL => {
-                    if let Ok(result) = to_hashmap_nfl(players_raw.as_ref()) {
-                        Ok(AllPlayers::NFL(result))
-                    } else {
-                        Err(SleeperError::DeserializationError("Error trying to deserialize from JSON raw string to HashMap<PlayerId, NflPlayer>".to_string()))
-                    }
-                }
-                SleeperSport::LCS => {
-                    todo!("Only NFL is supported currently. Consider opening a pull request!")
-                }
-                SleeperSport::NBA => {
-                    todo!("Only NFL is supported currently. Consider opening a pull request!")
-                }
-            },
-            Err(_) => Err(SleeperError::DeserializationError(String::from("String"))), // TODO lol
+        let (league, users, rosters, matchups) = futures::try_join!(
+            self.get_league(league_id),
+            self.get_users_in_league(league_id),
+            self.get_rosters(league_id),
+            matchups,
+        )?;
+
+        Ok(LeagueSnapshot::new(league, users, rosters, matchups))
+    }
+
+    pub async fn get_sport_state(&self, sport: SleeperSport) -> Result<SportState, SleeperError> {
+        let url = format!("{}/state/{}", self.base_url, &sport.to_string());
+
+        self.get_json(&url, "SportState").await
+    }
+
+    // Be careful, it's thicccc
+    pub async fn get_all_players(&self, sport: SleeperSport) -> Result<AllPlayers, SleeperError> {
+        let url = format!("{}/players/{}", self.base_url, &sport.to_string());
+
+        match sport {
+            SleeperSport::NFL => self
+                .get_json(&url, "HashMap<PlayerId, NflPlayer>")
+                .await
+                .map(AllPlayers::NFL),
+            SleeperSport::LCS => self
+                .get_json(&url, "HashMap<PlayerId, Value>")
+                .await
+                .map(AllPlayers::LCS),
+            SleeperSport::NBA => self
+                .get_json(&url, "HashMap<PlayerId, Value>")
+                .await
+                .map(AllPlayers::NBA),
         }
     }
 
@@ -161,12 +285,9 @@ impl Client {
         &self,
         sport: SleeperSport,
     ) -> Result<Value, SleeperError> {
-        let url = format!("{}/players/{}", BASE_URL, &sport.to_string());
+        let url = format!("{}/players/{}", self.base_url, &sport.to_string());
 
-        let res = match self.client.get(&url).send().await {
-            Ok(res) => res,
-            Err(e) => return Err(SleeperError::NetworkError(e.status())),
-        };
+        let res = self.get(&url).await?;
 
         // TODO - revisit type
         match res.text_with_charset("utf-8").await {
@@ -174,4 +295,408 @@ impl Client {
             Err(_) => Err(SleeperError::DeserializationError(String::from("String"))), // TODO lol
         }
     }
+
+    /// Stats for every player who recorded any in the given week.
+    pub async fn get_weekly_stats(
+        &self,
+        sport: SleeperSport,
+        season_type: SeasonType,
+        season: Season,
+        week: u8,
+    ) -> Result<HashMap<PlayerId, StatLine>, SleeperError> {
+        let url = format!(
+            "{}/stats/{}/{}/{}/{}",
+            self.base_url, sport, season_type, season, week
+        );
+
+        self.get_json(&url, "HashMap<PlayerId, StatLine>").await
+    }
+
+    /// Stat totals for every player across the whole season.
+    pub async fn get_season_stats(
+        &self,
+        sport: SleeperSport,
+        season_type: SeasonType,
+        season: Season,
+    ) -> Result<HashMap<PlayerId, StatLine>, SleeperError> {
+        let url = format!(
+            "{}/stats/{}/{}/{}",
+            self.base_url, sport, season_type, season
+        );
+
+        self.get_json(&url, "HashMap<PlayerId, StatLine>").await
+    }
+
+    /// Projected stats for every player in the given week.
+    pub async fn get_weekly_projections(
+        &self,
+        sport: SleeperSport,
+        season_type: SeasonType,
+        season: Season,
+        week: u8,
+    ) -> Result<HashMap<PlayerId, StatLine>, SleeperError> {
+        let url = format!(
+            "{}/projections/{}/{}/{}/{}",
+            self.base_url, sport, season_type, season, week
+        );
+
+        self.get_json(&url, "HashMap<PlayerId, StatLine>").await
+    }
+
+    /// Projected stat totals for every player across the whole season.
+    pub async fn get_season_projections(
+        &self,
+        sport: SleeperSport,
+        season_type: SeasonType,
+        season: Season,
+    ) -> Result<HashMap<PlayerId, StatLine>, SleeperError> {
+        let url = format!(
+            "{}/projections/{}/{}/{}",
+            self.base_url, sport, season_type, season
+        );
+
+        self.get_json(&url, "HashMap<PlayerId, StatLine>").await
+    }
+
+    /// The league's playoff bracket. Empty until Sleeper has generated it.
+    pub async fn get_winners_bracket(
+        &self,
+        league_id: &str,
+    ) -> Result<Vec<BracketMatchup>, SleeperError> {
+        let url = format!("{}/league/{}/winners_bracket", self.base_url, league_id);
+
+        let bracket: Option<Vec<BracketMatchup>> =
+            self.get_json(&url, "Vec<BracketMatchup>").await?;
+        Ok(bracket.unwrap_or_default())
+    }
+
+    /// The league's consolation bracket. Empty until Sleeper has generated it.
+    pub async fn get_losers_bracket(
+        &self,
+        league_id: &str,
+    ) -> Result<Vec<BracketMatchup>, SleeperError> {
+        let url = format!("{}/league/{}/losers_bracket", self.base_url, league_id);
+
+        let bracket: Option<Vec<BracketMatchup>> =
+            self.get_json(&url, "Vec<BracketMatchup>").await?;
+        Ok(bracket.unwrap_or_default())
+    }
+
+    /// A single season of a league: the league itself, its users, rosters and brackets.
+    pub async fn get_league_season(&self, league_id: &str) -> Result<LeagueSeason, SleeperError> {
+        let (league, users, rosters, winners_bracket, losers_bracket) = futures::try_join!(
+            self.get_league(league_id),
+            self.get_users_in_league(league_id),
+            self.get_rosters(league_id),
+            self.get_winners_bracket(league_id),
+            self.get_losers_bracket(league_id),
+        )?;
+
+        Ok(LeagueSeason {
+            league,
+            users,
+            rosters,
+            winners_bracket,
+            losers_bracket,
+        })
+    }
+
+    /// Every season of a league, found by following `previous_league_id`
+    /// back from `league_id` until the league's first season.
+    pub async fn get_league_history(&self, league_id: &str) -> Result<LeagueHistory, SleeperError> {
+        let mut seasons = Vec::new();
+        let mut visited = HashSet::new();
+        let mut next = Some(league_id.to_string());
+
+        while let Some(id) = next.take() {
+            // Guards against a malformed chain pointing back at itself
+            if !visited.insert(id.clone()) {
+                break;
+            }
+
+            let season = self.get_league_season(&id).await?;
+            next = season.league.previous_league().cloned();
+            seasons.push(season);
+        }
+
+        seasons.reverse();
+        Ok(LeagueHistory::from_seasons(seasons))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEEKLY_STATS: &str = include_str!("../tests/fixtures/stats_nfl_regular_2023_1.json");
+    const WEEKLY_PROJECTIONS: &str =
+        include_str!("../tests/fixtures/projections_nfl_regular_2023_1.json");
+
+    #[tokio::test]
+    async fn test_get_weekly_stats() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/stats/nfl/regular/2023/1")
+            .with_header("content-type", "application/json")
+            .with_body(WEEKLY_STATS)
+            .create_async()
+            .await;
+
+        let client = Client::with_base_url(&server.url());
+        let stats = client
+            .get_weekly_stats(SleeperSport::NFL, SeasonType::Regular, Season(2023), 1)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(stats.len(), 3);
+
+        let mahomes = &stats["4046"];
+        assert_eq!(mahomes.get("pass_yd"), 305.0);
+        assert_eq!(mahomes.get("pass_td"), 2.0);
+        assert_eq!(mahomes.get("rec"), 0.0);
+        assert_eq!(mahomes.pts_ppr(), Some(20.3));
+        assert_eq!(mahomes.games_played(), 1.0);
+
+        let kc = &stats["KC"];
+        assert_eq!(kc.get("pts_allow"), 21.0);
+        assert_eq!(kc.get("pts_allow_21_27"), 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_get_weekly_projections_and_score_them() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/projections/nfl/regular/2023/1")
+            .with_header("content-type", "application/json")
+            .with_body(WEEKLY_PROJECTIONS)
+            .create_async()
+            .await;
+
+        let client = Client::with_base_url(&server.url());
+        let projections = client
+            .get_weekly_projections(SleeperSport::NFL, SeasonType::Regular, Season(2023), 1)
+            .await
+            .unwrap();
+
+        let settings = ScoringSettings {
+            rec: 1.0,
+            rec_yd: 0.1,
+            rec_td: 6.0,
+            ..Default::default()
+        };
+        let jefferson = &projections["6794"];
+        assert_eq!(settings.score(jefferson).points, 20.85);
+        assert_eq!(jefferson.pts_ppr(), Some(20.85));
+    }
+
+    #[tokio::test]
+    async fn test_get_season_stats_reports_http_errors() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/stats/nfl/regular/2023")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let client = Client::with_base_url(&server.url());
+        let result = client
+            .get_season_stats(SleeperSport::NFL, SeasonType::Regular, Season(2023))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(SleeperError::NetworkError(Some(status))) if status == http::StatusCode::NOT_FOUND
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_league_reports_http_errors() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/league/missing")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let client = Client::with_base_url(&server.url());
+
+        assert!(matches!(
+            client.get_league("missing").await,
+            Err(SleeperError::NetworkError(Some(status))) if status == http::StatusCode::NOT_FOUND
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_league_history_follows_previous_league_id() {
+        let mut server = mockito::Server::new_async().await;
+        let league_json = |id: &str, season: &str, previous: Option<&str>| {
+            let mut league: serde_json::Value =
+                serde_json::from_str(include_str!("../tests/fixtures/league.json")).unwrap();
+            league["league_id"] = id.into();
+            league["season"] = season.into();
+            league["previous_league_id"] = previous.into();
+            league.to_string()
+        };
+        let seasons = [("2", "2023", Some("1")), ("1", "2022", Some("0"))];
+
+        for (id, season, previous) in seasons {
+            server
+                .mock("GET", format!("/league/{}", id).as_str())
+                .with_body(league_json(id, season, previous))
+                .create_async()
+                .await;
+            server
+                .mock("GET", format!("/league/{}/users", id).as_str())
+                .with_body(format!(
+                    r#"[{{"user_id": "u1", "username": "u{0}", "settings": null, "metadata": {{}},
+                        "is_owner": true, "is_bot": false, "league_id": "{0}",
+                        "display_name": "Name{0}", "avatar": null}}]"#,
+                    id
+                ))
+                .create_async()
+                .await;
+            server
+                .mock("GET", format!("/league/{}/rosters", id).as_str())
+                .with_body(format!(
+                    r#"[{{"taxi": null, "starters": [], "settings": {{"wins": 3}}, "roster_id": {0},
+                        "reserve": null, "players": [], "player_map": null, "owner_id": "u1",
+                        "metadata": null, "league_id": "{0}", "keepers": null, "co_owners": null}}]"#,
+                    id
+                ))
+                .create_async()
+                .await;
+            server
+                .mock("GET", format!("/league/{}/winners_bracket", id).as_str())
+                .with_body("null")
+                .create_async()
+                .await;
+            server
+                .mock("GET", format!("/league/{}/losers_bracket", id).as_str())
+                .with_body("[]")
+                .create_async()
+                .await;
+        }
+
+        let client = Client::with_base_url(&server.url());
+        let history = client.get_league_history("2").await.unwrap();
+
+        let years: Vec<Season> = history.seasons.iter().map(|s| s.league.season).collect();
+        assert_eq!(years, vec![Season(2022), Season(2023)]);
+        assert!(history.seasons[0].league.previous_league().is_none());
+
+        let owner = &history.owners[0];
+        assert_eq!(history.owners.len(), 1);
+        assert_eq!(owner.record().wins, 6);
+        let roster_ids: Vec<u8> = owner.seasons.iter().map(|s| s.roster_id).collect();
+        assert_eq!(roster_ids, vec![1, 2]);
+        assert_eq!(owner.display_name(), Some("Name2"));
+    }
+
+    #[tokio::test]
+    async fn test_get_season_matchups_keeps_successful_weeks() {
+        let mut server = mockito::Server::new_async().await;
+        for week in 1..=3 {
+            server
+                .mock("GET", format!("/league/1/matchups/{}", week).as_str())
+                .with_body(format!(
+                    r#"[{{"starters_points": [], "starters": [], "roster_id": 1, "points": {}.5,
+                        "players_points": {{}}, "players": [], "matchup_id": 1, "custom_points": null}}]"#,
+                    week
+                ))
+                .create_async()
+                .await;
+        }
+        server
+            .mock("GET", "/league/1/matchups/4")
+            .with_status(500)
+            .with_body("upstream unavailable")
+            .create_async()
+            .await;
+
+        let client = Client::with_base_url(&server.url()).with_concurrency_limit(2);
+        let season = client.get_season_matchups("1", 1..=4).await;
+
+        assert!(!season.is_complete());
+        assert_eq!(
+            season.matchups.keys().copied().collect::<Vec<u8>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(season.matchups[&3][0].points, 3.5);
+        assert!(season.errors.contains_key(&4));
+    }
+
+    #[tokio::test]
+    async fn test_get_transactions() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/league/1/transactions/3")
+            .with_body(include_str!(
+                "../tests/fixtures/transactions_nfl_week_3.json"
+            ))
+            .create_async()
+            .await;
+
+        let client = Client::with_base_url(&server.url());
+        let transactions = client.get_transactions("1", 3).await.unwrap();
+
+        assert_eq!(transactions.len(), 5);
+        let trade = &transactions[0];
+        assert_eq!(trade.transaction_type, TransactionType::Trade);
+        assert_eq!(trade.added_to(2), vec!["6794", "7564"]);
+        assert_eq!(trade.draft_picks[0].season, Season(2024));
+        assert_eq!(trade.waiver_budget[0].amount, 10);
+
+        let failed = &transactions[2];
+        assert_eq!(failed.status, TransactionStatus::Failed);
+        assert_eq!(failed.settings.as_ref().unwrap().waiver_bid, Some(18));
+        assert_eq!(transactions[4].transaction_type, TransactionType::FreeAgent);
+    }
+
+    #[tokio::test]
+    async fn test_get_draft_picks() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/draft/257270643320426496/picks")
+            .with_body(
+                r#"[{
+                    "player_id": "2391", "picked_by": "234343434", "roster_id": "1",
+                    "round": 1, "draft_slot": 5, "pick_no": 5, "is_keeper": null,
+                    "draft_id": "257270643320426496",
+                    "metadata": {
+                        "team": "NE", "status": "Active", "sport": "nfl", "position": "RB",
+                        "player_id": "2391", "number": "28", "news_updated": "1513007102037",
+                        "last_name": "White", "injury_status": "", "first_name": "James"
+                    }
+                }]"#,
+            )
+            .create_async()
+            .await;
+
+        let client = Client::with_base_url(&server.url());
+        let picks = client.get_draft_picks("257270643320426496").await.unwrap();
+
+        assert_eq!(picks[0].roster_id, Some(1));
+        assert_eq!(picks[0].player_name().as_deref(), Some("James White"));
+        assert_eq!(picks[0].position(), Some(Position::RB));
+        assert_eq!(picks[0].team(), Some(NflTeam::NE));
+        assert_eq!(picks[0].amount(), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_user_not_found() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/user/nobody")
+            .with_body("null")
+            .create_async()
+            .await;
+
+        let client = Client::with_base_url(&server.url());
+
+        assert!(matches!(
+            client.get_user("nobody").await,
+            Err(SleeperError::UserNotFound(name)) if name == "nobody"
+        ));
+    }
 }
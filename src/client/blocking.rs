@@ -0,0 +1,229 @@
+//! A synchronous client for programs without an async runtime of their own.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use serde_json::Value;
+use tokio::runtime::{Builder, Runtime};
+
+use super::SeasonMatchups;
+use crate::data::*;
+use crate::history::{LeagueHistory, LeagueSeason};
+use crate::snapshot::LeagueSnapshot;
+
+/// The same endpoints as `client::Client`, blocking until each completes.
+///
+/// Requests run on a single-threaded Tokio runtime owned by the client. It
+/// must not be used from within an async runtime, where blocking would panic.
+pub struct Client {
+    inner: super::Client,
+    runtime: Runtime,
+}
+
+impl Client {
+    pub fn new() -> Result<Self, SleeperError> {
+        Self::from_async(super::Client::new())
+    }
+
+    /// See `client::Client::with_base_url`.
+    pub fn with_base_url(base_url: &str) -> Result<Self, SleeperError> {
+        Self::from_async(super::Client::with_base_url(base_url))
+    }
+
+    /// Wrap an async client, keeping its configuration. Fails with a
+    /// `RuntimeError` if the runtime cannot be started.
+    pub fn from_async(inner: super::Client) -> Result<Self, SleeperError> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| SleeperError::RuntimeError(e.to_string()))?;
+
+        Ok(Client { inner, runtime })
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    pub fn get_league(&self, id: &str) -> Result<League, SleeperError> {
+        self.block_on(self.inner.get_league(id))
+    }
+
+    pub fn get_rosters(&self, league_id: &str) -> Result<Vec<Roster>, SleeperError> {
+        self.block_on(self.inner.get_rosters(league_id))
+    }
+
+    pub fn get_users_in_league(&self, league_id: &str) -> Result<Vec<SleeperUser>, SleeperError> {
+        self.block_on(self.inner.get_users_in_league(league_id))
+    }
+
+    pub fn get_matchups(&self, league_id: &str, week: u8) -> Result<Vec<Matchup>, SleeperError> {
+        self.block_on(self.inner.get_matchups(league_id, week))
+    }
+
+    pub fn get_season_matchups(
+        &self,
+        league_id: &str,
+        weeks: impl IntoIterator<Item = u8>,
+    ) -> SeasonMatchups {
+        self.block_on(self.inner.get_season_matchups(league_id, weeks))
+    }
+
+    pub fn get_transactions(
+        &self,
+        league_id: &str,
+        week: u8,
+    ) -> Result<Vec<Transaction>, SleeperError> {
+        self.block_on(self.inner.get_transactions(league_id, week))
+    }
+
+    pub fn get_user(&self, username_or_id: &str) -> Result<User, SleeperError> {
+        self.block_on(self.inner.get_user(username_or_id))
+    }
+
+    pub fn get_user_leagues(
+        &self,
+        user_id: &str,
+        sport: SleeperSport,
+        season: Season,
+    ) -> Result<Vec<League>, SleeperError> {
+        self.block_on(self.inner.get_user_leagues(user_id, sport, season))
+    }
+
+    pub fn get_league_drafts(&self, league_id: &str) -> Result<Vec<Draft>, SleeperError> {
+        self.block_on(self.inner.get_league_drafts(league_id))
+    }
+
+    pub fn get_draft(&self, draft_id: &str) -> Result<Draft, SleeperError> {
+        self.block_on(self.inner.get_draft(draft_id))
+    }
+
+    pub fn get_draft_picks(&self, draft_id: &str) -> Result<Vec<DraftPick>, SleeperError> {
+        self.block_on(self.inner.get_draft_picks(draft_id))
+    }
+
+    pub fn get_traded_picks(&self, league_id: &str) -> Result<Vec<TradedPick>, SleeperError> {
+        self.block_on(self.inner.get_traded_picks(league_id))
+    }
+
+    pub fn get_league_snapshot(
+        &self,
+        league_id: &str,
+        weeks: impl IntoIterator<Item = u8>,
+    ) -> Result<LeagueSnapshot, SleeperError> {
+        self.block_on(self.inner.get_league_snapshot(league_id, weeks))
+    }
+
+    pub fn get_sport_state(&self, sport: SleeperSport) -> Result<SportState, SleeperError> {
+        self.block_on(self.inner.get_sport_state(sport))
+    }
+
+    pub fn get_all_players(&self, sport: SleeperSport) -> Result<AllPlayers, SleeperError> {
+        self.block_on(self.inner.get_all_players(sport))
+    }
+
+    pub fn get_all_players_unparsed(&self, sport: SleeperSport) -> Result<Value, SleeperError> {
+        self.block_on(self.inner.get_all_players_unparsed(sport))
+    }
+
+    pub fn get_weekly_stats(
+        &self,
+        sport: SleeperSport,
+        season_type: SeasonType,
+        season: Season,
+        week: u8,
+    ) -> Result<HashMap<PlayerId, StatLine>, SleeperError> {
+        self.block_on(
+            self.inner
+                .get_weekly_stats(sport, season_type, season, week),
+        )
+    }
+
+    pub fn get_season_stats(
+        &self,
+        sport: SleeperSport,
+        season_type: SeasonType,
+        season: Season,
+    ) -> Result<HashMap<PlayerId, StatLine>, SleeperError> {
+        self.block_on(self.inner.get_season_stats(sport, season_type, season))
+    }
+
+    pub fn get_weekly_projections(
+        &self,
+        sport: SleeperSport,
+        season_type: SeasonType,
+        season: Season,
+        week: u8,
+    ) -> Result<HashMap<PlayerId, StatLine>, SleeperError> {
+        self.block_on(
+            self.inner
+                .get_weekly_projections(sport, season_type, season, week),
+        )
+    }
+
+    pub fn get_season_projections(
+        &self,
+        sport: SleeperSport,
+        season_type: SeasonType,
+        season: Season,
+    ) -> Result<HashMap<PlayerId, StatLine>, SleeperError> {
+        self.block_on(
+            self.inner
+                .get_season_projections(sport, season_type, season),
+        )
+    }
+
+    pub fn get_winners_bracket(
+        &self,
+        league_id: &str,
+    ) -> Result<Vec<BracketMatchup>, SleeperError> {
+        self.block_on(self.inner.get_winners_bracket(league_id))
+    }
+
+    pub fn get_losers_bracket(&self, league_id: &str) -> Result<Vec<BracketMatchup>, SleeperError> {
+        self.block_on(self.inner.get_losers_bracket(league_id))
+    }
+
+    pub fn get_league_season(&self, league_id: &str) -> Result<LeagueSeason, SleeperError> {
+        self.block_on(self.inner.get_league_season(league_id))
+    }
+
+    pub fn get_league_history(&self, league_id: &str) -> Result<LeagueHistory, SleeperError> {
+        self.block_on(self.inner.get_league_history(league_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocking_get_matchups() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/league/1/matchups/2")
+            .with_body(
+                r#"[{"starters_points": [], "starters": [], "roster_id": 1, "points": 88.5,
+                    "players_points": {}, "players": [], "matchup_id": 1, "custom_points": null}]"#,
+            )
+            .create();
+
+        let client = Client::with_base_url(&server.url()).unwrap();
+        let matchups = client.get_matchups("1", 2).unwrap();
+
+        assert_eq!(matchups[0].points, 88.5);
+    }
+
+    #[test]
+    fn test_blocking_reports_http_errors() {
+        let mut server = mockito::Server::new();
+        server.mock("GET", "/league/1").with_status(404).create();
+
+        let client = Client::with_base_url(&server.url()).unwrap();
+
+        assert!(matches!(
+            client.get_league("1"),
+            Err(SleeperError::NetworkError(Some(status))) if status == http::StatusCode::NOT_FOUND
+        ));
+    }
+}
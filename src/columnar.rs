@@ -0,0 +1,379 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BooleanArray, Float64Array, Int32Array, Int8Array, ListArray, ListBuilder,
+    MapBuilder, StringArray, StringBuilder, TimestampMillisecondArray, UInt16Array, UInt32Array,
+    UInt64Array, UInt8Array, UInt8Builder,
+};
+use arrow::datatypes::UInt8Type;
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+
+use crate::data::*;
+use crate::export::{matchup_rows, player_rows, PlayerRow};
+
+/// Every NFL player ordered by `player_id`, with the same columns as the CSV
+/// export except that `fantasy_positions` is a list and `news_updated` a timestamp.
+pub fn players_batch(players: &AllPlayers) -> Result<RecordBatch, SleeperError> {
+    let rows = player_rows(players)?;
+
+    let strings = |f: fn(&PlayerRow) -> Option<&str>| -> ArrayRef {
+        Arc::new(rows.iter().map(f).collect::<StringArray>())
+    };
+    let mut fantasy_positions = ListBuilder::new(StringBuilder::new());
+    for row in &rows {
+        let positions = row.fantasy_positions.as_deref();
+        for position in positions.into_iter().flat_map(|p| p.split(';')) {
+            if !position.is_empty() {
+                fantasy_positions.values().append_value(position);
+            }
+        }
+        fantasy_positions.append(positions.is_some());
+    }
+
+    RecordBatch::try_from_iter([
+        ("player_id", strings(|r| Some(&r.player_id))),
+        ("full_name", strings(|r| r.full_name.as_deref())),
+        ("first_name", strings(|r| Some(&r.first_name))),
+        ("last_name", strings(|r| Some(&r.last_name))),
+        ("position", strings(|r| r.position.as_deref())),
+        (
+            "fantasy_positions",
+            Arc::new(fantasy_positions.finish()) as ArrayRef,
+        ),
+        ("team", strings(|r| r.team.as_deref())),
+        (
+            "number",
+            Arc::new(rows.iter().map(|r| r.number).collect::<UInt8Array>()),
+        ),
+        ("status", strings(|r| r.status.as_deref())),
+        (
+            "active",
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.active))
+                    .collect::<BooleanArray>(),
+            ),
+        ),
+        ("injury_status", strings(|r| r.injury_status.as_deref())),
+        (
+            "injury_body_part",
+            strings(|r| r.injury_body_part.as_deref()),
+        ),
+        ("injury_notes", strings(|r| r.injury_notes.as_deref())),
+        (
+            "injury_start_date",
+            strings(|r| r.injury_start_date.as_deref()),
+        ),
+        (
+            "practice_participation",
+            strings(|r| r.practice_participation.as_deref()),
+        ),
+        (
+            "practice_description",
+            strings(|r| r.practice_description.as_deref()),
+        ),
+        (
+            "depth_chart_position",
+            strings(|r| r.depth_chart_position.as_deref()),
+        ),
+        (
+            "depth_chart_order",
+            Arc::new(
+                rows.iter()
+                    .map(|r| r.depth_chart_order)
+                    .collect::<UInt8Array>(),
+            ),
+        ),
+        (
+            "search_rank",
+            Arc::new(rows.iter().map(|r| r.search_rank).collect::<UInt64Array>()),
+        ),
+        (
+            "age",
+            Arc::new(rows.iter().map(|r| r.age).collect::<UInt8Array>()),
+        ),
+        ("birth_date", strings(|r| r.birth_date.as_deref())),
+        (
+            "years_exp",
+            Arc::new(rows.iter().map(|r| r.years_exp).collect::<Int8Array>()),
+        ),
+        (
+            "height_inches",
+            Arc::new(rows.iter().map(|r| r.height_inches).collect::<UInt8Array>()),
+        ),
+        (
+            "weight_lbs",
+            Arc::new(rows.iter().map(|r| r.weight_lbs).collect::<UInt16Array>()),
+        ),
+        ("college", strings(|r| r.college.as_deref())),
+        ("high_school", strings(|r| r.high_school.as_deref())),
+        ("birth_city", strings(|r| r.birth_city.as_deref())),
+        ("birth_state", strings(|r| r.birth_state.as_deref())),
+        ("birth_country", strings(|r| r.birth_country.as_deref())),
+        (
+            "news_updated",
+            Arc::new(
+                rows.iter()
+                    .map(|r| r.news_updated.map(|t| t as i64))
+                    .collect::<TimestampMillisecondArray>(),
+            ),
+        ),
+        (
+            "espn_id",
+            Arc::new(rows.iter().map(|r| r.espn_id).collect::<UInt64Array>()),
+        ),
+        (
+            "yahoo_id",
+            Arc::new(rows.iter().map(|r| r.yahoo_id).collect::<Int32Array>()),
+        ),
+        ("sportradar_id", strings(|r| r.sportradar_id.as_deref())),
+        ("gsis_id", strings(|r| r.gsis_id.as_deref())),
+        (
+            "rotowire_id",
+            Arc::new(rows.iter().map(|r| r.rotowire_id).collect::<UInt32Array>()),
+        ),
+        (
+            "rotoworld_id",
+            Arc::new(rows.iter().map(|r| r.rotoworld_id).collect::<UInt64Array>()),
+        ),
+        (
+            "stats_id",
+            Arc::new(rows.iter().map(|r| r.stats_id).collect::<UInt32Array>()),
+        ),
+        (
+            "swish_id",
+            Arc::new(rows.iter().map(|r| r.swish_id).collect::<UInt32Array>()),
+        ),
+        (
+            "fantasy_data_id",
+            Arc::new(
+                rows.iter()
+                    .map(|r| r.fantasy_data_id)
+                    .collect::<UInt64Array>(),
+            ),
+        ),
+    ])
+    .map_err(arrow_error)
+}
+
+/// One row per player per roster per week, in the order of `export::matchup_rows`.
+pub fn matchups_batch(matchups: &BTreeMap<u8, Vec<Matchup>>) -> Result<RecordBatch, SleeperError> {
+    let rows = matchup_rows(matchups);
+
+    RecordBatch::try_from_iter([
+        (
+            "week",
+            Arc::new(rows.iter().map(|r| Some(r.week)).collect::<UInt8Array>()) as ArrayRef,
+        ),
+        (
+            "matchup_id",
+            Arc::new(rows.iter().map(|r| r.matchup_id).collect::<UInt8Array>()),
+        ),
+        (
+            "roster_id",
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.roster_id))
+                    .collect::<UInt8Array>(),
+            ),
+        ),
+        (
+            "player_id",
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(&r.player_id))
+                    .collect::<StringArray>(),
+            ),
+        ),
+        (
+            "points",
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.points))
+                    .collect::<Float64Array>(),
+            ),
+        ),
+        (
+            "started",
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.started))
+                    .collect::<BooleanArray>(),
+            ),
+        ),
+    ])
+    .map_err(arrow_error)
+}
+
+/// One row per transaction, oldest first. `adds` and `drops` are maps of
+/// player to roster.
+pub fn transactions_batch(transactions: &[Transaction]) -> Result<RecordBatch, SleeperError> {
+    let mut transactions: Vec<&Transaction> = transactions.iter().collect();
+    transactions.sort_by_key(|t| t.created);
+
+    let strings = |f: fn(&Transaction) -> Option<String>| -> ArrayRef {
+        Arc::new(transactions.iter().map(|t| f(t)).collect::<StringArray>())
+    };
+    let timestamps = |f: fn(&Transaction) -> Timestamp| -> ArrayRef {
+        Arc::new(
+            transactions
+                .iter()
+                .map(|t| Some(f(t).0 as i64))
+                .collect::<TimestampMillisecondArray>(),
+        )
+    };
+    let moves =
+        |f: fn(&Transaction) -> &Option<HashMap<PlayerId, u8>>| -> Result<ArrayRef, ArrowError> {
+            let mut builder = MapBuilder::new(None, StringBuilder::new(), UInt8Builder::new());
+            for transaction in &transactions {
+                let mut players: Vec<(&PlayerId, &u8)> = f(transaction).iter().flatten().collect();
+                players.sort();
+                for (player_id, roster_id) in players {
+                    builder.keys().append_value(player_id);
+                    builder.values().append_value(*roster_id);
+                }
+                builder.append(f(transaction).is_some())?;
+            }
+            Ok(Arc::new(builder.finish()))
+        };
+
+    RecordBatch::try_from_iter([
+        (
+            "transaction_id",
+            strings(|t| Some(t.transaction_id.clone())),
+        ),
+        ("type", strings(|t| Some(t.transaction_type.to_string()))),
+        ("status", strings(|t| Some(t.status.to_string()))),
+        (
+            "week",
+            Arc::new(
+                transactions
+                    .iter()
+                    .map(|t| Some(t.leg))
+                    .collect::<UInt8Array>(),
+            ),
+        ),
+        ("created", timestamps(|t| t.created)),
+        ("status_updated", timestamps(|t| t.status_updated)),
+        ("creator", strings(|t| t.creator.clone())),
+        (
+            "roster_ids",
+            Arc::new(ListArray::from_iter_primitive::<UInt8Type, _, _>(
+                transactions
+                    .iter()
+                    .map(|t| Some(t.roster_ids.iter().copied().map(Some))),
+            )),
+        ),
+        ("adds", moves(|t| &t.adds).map_err(arrow_error)?),
+        ("drops", moves(|t| &t.drops).map_err(arrow_error)?),
+        (
+            "waiver_bid",
+            Arc::new(
+                transactions
+                    .iter()
+                    .map(|t| t.settings.as_ref().and_then(|s| s.waiver_bid))
+                    .collect::<UInt32Array>(),
+            ),
+        ),
+    ])
+    .map_err(arrow_error)
+}
+
+/// Write `batch` as a Parquet file.
+pub fn write_parquet<W: Write + Send>(writer: W, batch: &RecordBatch) -> Result<(), SleeperError> {
+    let mut parquet = ArrowWriter::try_new(writer, batch.schema(), None)
+        .map_err(|e| SleeperError::ExportError(e.to_string()))?;
+    parquet
+        .write(batch)
+        .map_err(|e| SleeperError::ExportError(e.to_string()))?;
+    parquet
+        .close()
+        .map(|_| ())
+        .map_err(|e| SleeperError::ExportError(e.to_string()))
+}
+
+fn arrow_error(e: ArrowError) -> SleeperError {
+    SleeperError::ExportError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::{DataType, TimeUnit};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use crate::test_support::{matchup_with_players, snapshot};
+
+    #[test]
+    fn test_players_batch_is_typed() {
+        let players = AllPlayers::NFL(snapshot().players.into_iter().collect());
+        let batch = players_batch(&players).unwrap();
+        let schema = batch.schema();
+
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(schema.field(0).name(), "player_id");
+        assert_eq!(
+            schema.field_with_name("news_updated").unwrap().data_type(),
+            &DataType::Timestamp(TimeUnit::Millisecond, None)
+        );
+        assert_eq!(
+            schema.field_with_name("espn_id").unwrap().data_type(),
+            &DataType::UInt64
+        );
+        let names = batch
+            .column_by_name("full_name")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!(names.value(0), "Patrick Mahomes");
+        assert!(batch
+            .column_by_name("fantasy_positions")
+            .unwrap()
+            .is_null(0));
+    }
+
+    #[test]
+    fn test_matchups_and_transactions_to_parquet() {
+        let matchups: BTreeMap<u8, Vec<Matchup>> = [(
+            3,
+            vec![matchup_with_players(
+                1,
+                &[("4046", 25.5, true), ("6794", 4.0, false)],
+            )],
+        )]
+        .into_iter()
+        .collect();
+        let batch = matchups_batch(&matchups).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        let started = batch.column_by_name("started").unwrap().as_boolean();
+        assert!(started.value(0) && !started.value(1));
+
+        let transactions: Vec<Transaction> = serde_json::from_str(include_str!(
+            "../tests/fixtures/transactions_nfl_week_3.json"
+        ))
+        .unwrap();
+        let batch = transactions_batch(&transactions).unwrap();
+        assert_eq!(batch.num_rows(), 5);
+        let adds = batch.column_by_name("adds").unwrap().as_map();
+        let types = batch.column_by_name("type").unwrap().as_string::<i32>();
+        assert_eq!(types.value(3), "trade");
+        assert_eq!(adds.value(3).len(), 3);
+
+        let mut parquet = Vec::new();
+        write_parquet(&mut parquet, &batch).unwrap();
+        let restored: Vec<RecordBatch> =
+            ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(parquet))
+                .unwrap()
+                .build()
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+        assert_eq!(restored[0].schema(), batch.schema());
+        assert_eq!(restored[0].num_rows(), 5);
+    }
+}
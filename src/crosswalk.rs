@@ -0,0 +1,310 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::*;
+use crate::export;
+
+/// A data provider whose player IDs Sleeper carries on `NflPlayer`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdProvider {
+    Espn,
+    Yahoo,
+    Sportradar,
+    Gsis,
+    Rotowire,
+    Rotoworld,
+    Stats,
+    Swish,
+    FantasyData,
+}
+
+impl IdProvider {
+    pub const ALL: [IdProvider; 9] = [
+        IdProvider::Espn,
+        IdProvider::Yahoo,
+        IdProvider::Sportradar,
+        IdProvider::Gsis,
+        IdProvider::Rotowire,
+        IdProvider::Rotoworld,
+        IdProvider::Stats,
+        IdProvider::Swish,
+        IdProvider::FantasyData,
+    ];
+
+    /// This provider's ID for `player`, as a string regardless of how Sleeper types it.
+    pub fn id_of(&self, player: &NflPlayer) -> Option<String> {
+        let id = match self {
+            IdProvider::Espn => player.espn_id.map(|id| id.to_string()),
+            IdProvider::Yahoo => player.yahoo_id.map(|id| id.to_string()),
+            IdProvider::Sportradar => player.sportradar_id.clone(),
+            IdProvider::Gsis => player.gsis_id.clone(),
+            IdProvider::Rotowire => player.rotowire_id.map(|id| id.to_string()),
+            IdProvider::Rotoworld => player.rotoworld_id.map(|id| id.to_string()),
+            IdProvider::Stats => player.stats_id.map(|id| id.to_string()),
+            IdProvider::Swish => player.swish_id.map(|id| id.to_string()),
+            IdProvider::FantasyData => player.fantasy_data_id.map(|id| id.to_string()),
+        };
+
+        // GSIS IDs in particular tend to come padded with whitespace
+        id.map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+    }
+}
+
+/// Every external ID known for a single Sleeper player. This is also the row
+/// shape of the CSV and JSON exports, so field order is the column order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ExternalIds {
+    pub player_id: PlayerId,
+    pub full_name: Option<String>,
+    pub espn_id: Option<String>,
+    pub yahoo_id: Option<String>,
+    pub sportradar_id: Option<String>,
+    pub gsis_id: Option<String>,
+    pub rotowire_id: Option<String>,
+    pub rotoworld_id: Option<String>,
+    pub stats_id: Option<String>,
+    pub swish_id: Option<String>,
+    pub fantasy_data_id: Option<String>,
+}
+
+impl ExternalIds {
+    pub fn get(&self, provider: IdProvider) -> Option<&str> {
+        let id = match provider {
+            IdProvider::Espn => &self.espn_id,
+            IdProvider::Yahoo => &self.yahoo_id,
+            IdProvider::Sportradar => &self.sportradar_id,
+            IdProvider::Gsis => &self.gsis_id,
+            IdProvider::Rotowire => &self.rotowire_id,
+            IdProvider::Rotoworld => &self.rotoworld_id,
+            IdProvider::Stats => &self.stats_id,
+            IdProvider::Swish => &self.swish_id,
+            IdProvider::FantasyData => &self.fantasy_data_id,
+        };
+        id.as_deref()
+    }
+
+    fn from_player(player: &NflPlayer) -> Self {
+        ExternalIds {
+            player_id: player.player_id.clone(),
+            full_name: player.full_name.clone(),
+            espn_id: IdProvider::Espn.id_of(player),
+            yahoo_id: IdProvider::Yahoo.id_of(player),
+            sportradar_id: IdProvider::Sportradar.id_of(player),
+            gsis_id: IdProvider::Gsis.id_of(player),
+            rotowire_id: IdProvider::Rotowire.id_of(player),
+            rotoworld_id: IdProvider::Rotoworld.id_of(player),
+            stats_id: IdProvider::Stats.id_of(player),
+            swish_id: IdProvider::Swish.id_of(player),
+            fantasy_data_id: IdProvider::FantasyData.id_of(player),
+        }
+    }
+}
+
+/// Maps the IDs of other data providers to Sleeper `PlayerId`s and back.
+///
+/// If two Sleeper players claim the same external ID (it happens with stale
+/// entries), the one with the numerically lower `PlayerId` wins. Team defense
+/// IDs, which are not numeric, lose to every player.
+#[derive(Clone, Debug, Default)]
+pub struct IdCrosswalk {
+    rows: BTreeMap<PlayerId, ExternalIds>,
+    to_sleeper: HashMap<IdProvider, HashMap<String, PlayerId>>,
+}
+
+impl IdCrosswalk {
+    /// Build a crosswalk from `AllPlayers::NFL`. Other sports produce an empty crosswalk.
+    pub fn new(players: &AllPlayers) -> Self {
+        match players {
+            AllPlayers::NFL(players) => Self::from_players(players.values()),
+            AllPlayers::LCS(_) | AllPlayers::NBA(_) => IdCrosswalk::default(),
+        }
+    }
+
+    pub fn from_players<'a>(players: impl IntoIterator<Item = &'a NflPlayer>) -> Self {
+        let rows: BTreeMap<PlayerId, ExternalIds> = players
+            .into_iter()
+            .map(|player| (player.player_id.clone(), ExternalIds::from_player(player)))
+            .collect();
+
+        let mut by_player_id: Vec<&ExternalIds> = rows.values().collect();
+        by_player_id.sort_by_key(|row| {
+            let numeric = row.player_id.parse::<u64>().unwrap_or(u64::MAX);
+            (numeric, &row.player_id)
+        });
+
+        let mut to_sleeper: HashMap<IdProvider, HashMap<String, PlayerId>> = HashMap::new();
+        for row in by_player_id {
+            for provider in IdProvider::ALL {
+                if let Some(id) = row.get(provider) {
+                    to_sleeper
+                        .entry(provider)
+                        .or_default()
+                        .entry(id.to_string())
+                        .or_insert_with(|| row.player_id.clone());
+                }
+            }
+        }
+
+        IdCrosswalk { rows, to_sleeper }
+    }
+
+    /// The Sleeper `PlayerId` for `provider`'s player `id`.
+    pub fn to_sleeper(&self, provider: IdProvider, id: &str) -> Option<&PlayerId> {
+        self.to_sleeper.get(&provider)?.get(id.trim())
+    }
+
+    /// `provider`'s ID for the Sleeper player `player_id`.
+    pub fn from_sleeper(&self, player_id: &str, provider: IdProvider) -> Option<&str> {
+        self.rows.get(player_id)?.get(provider)
+    }
+
+    /// Translate an ID straight from one provider to another.
+    pub fn translate(&self, from: IdProvider, id: &str, to: IdProvider) -> Option<&str> {
+        let player_id = self.to_sleeper(from, id)?;
+        self.from_sleeper(player_id, to)
+    }
+
+    pub fn external_ids(&self, player_id: &str) -> Option<&ExternalIds> {
+        self.rows.get(player_id)
+    }
+
+    /// Every row of the crosswalk, ordered by `PlayerId`.
+    pub fn rows(&self) -> impl Iterator<Item = &ExternalIds> {
+        self.rows.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Write the full crosswalk as CSV with a header row, one row per player.
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), SleeperError> {
+        export::write_csv(writer, self.rows().cloned())
+    }
+
+    /// Write the full crosswalk as a JSON array of `ExternalIds`.
+    pub fn write_json<W: Write>(&self, writer: W) -> Result<(), SleeperError> {
+        let rows: Vec<&ExternalIds> = self.rows().collect();
+        serde_json::to_writer(writer, &rows).map_err(|e| SleeperError::ExportError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players() -> Vec<NflPlayer> {
+        let json_str = r#"
+        [
+            {
+                "player_id": "4046",
+                "first_name": "Patrick",
+                "last_name": "Mahomes",
+                "full_name": "Patrick Mahomes",
+                "sport": "nfl",
+                "active": true,
+                "espn_id": 3139477,
+                "yahoo_id": 30123,
+                "sportradar_id": "11cad59d-90dd-449c-a839-dddaba4fe16c",
+                "gsis_id": " 00-0033873",
+                "rotowire_id": 11839,
+                "stats_id": 839031
+            },
+            {
+                "player_id": "6794",
+                "first_name": "Justin",
+                "last_name": "Jefferson",
+                "full_name": "Justin Jefferson",
+                "sport": "nfl",
+                "active": true,
+                "espn_id": 4262921,
+                "fantasy_data_id": 21685
+            }
+        ]"#;
+
+        serde_json::from_str(json_str).unwrap()
+    }
+
+    #[test]
+    fn test_crosswalk_resolves_both_directions() {
+        let players = players();
+        let crosswalk = IdCrosswalk::from_players(&players);
+
+        assert_eq!(
+            crosswalk.to_sleeper(IdProvider::Espn, "3139477"),
+            Some(&"4046".to_string())
+        );
+        assert_eq!(
+            crosswalk.to_sleeper(IdProvider::Gsis, "00-0033873"),
+            Some(&"4046".to_string())
+        );
+        assert_eq!(
+            crosswalk.from_sleeper("6794", IdProvider::FantasyData),
+            Some("21685")
+        );
+        assert_eq!(crosswalk.from_sleeper("6794", IdProvider::Yahoo), None);
+        assert_eq!(
+            crosswalk.translate(IdProvider::Yahoo, "30123", IdProvider::Sportradar),
+            Some("11cad59d-90dd-449c-a839-dddaba4fe16c")
+        );
+        assert_eq!(crosswalk.to_sleeper(IdProvider::Espn, "1"), None);
+    }
+
+    #[test]
+    fn test_crosswalk_prefers_numerically_lower_player_id() {
+        let mut players = players();
+        let mut stale = players[1].clone();
+        stale.player_id = "10000".to_string();
+        players[1].player_id = "999".to_string();
+        players.push(stale);
+
+        let crosswalk = IdCrosswalk::from_players(&players);
+        assert_eq!(
+            crosswalk.to_sleeper(IdProvider::Espn, "4262921"),
+            Some(&"999".to_string())
+        );
+    }
+
+    #[test]
+    fn test_crosswalk_exports() {
+        let players = players();
+        let crosswalk = IdCrosswalk::from_players(&players);
+
+        let mut csv = Vec::new();
+        crosswalk.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("player_id,full_name,espn_id,yahoo_id,sportradar_id,gsis_id,rotowire_id,rotoworld_id,stats_id,swish_id,fantasy_data_id")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("4046,Patrick Mahomes,3139477,30123,11cad59d-90dd-449c-a839-dddaba4fe16c,00-0033873,11839,,839031,,")
+        );
+        assert_eq!(lines.count(), 1);
+
+        let mut json = Vec::new();
+        crosswalk.write_json(&mut json).unwrap();
+        let rows: Vec<ExternalIds> = serde_json::from_slice(&json).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].espn_id.as_deref(), Some("4262921"));
+    }
+
+    #[test]
+    fn test_empty_crosswalk_csv_has_header() {
+        let mut csv = Vec::new();
+        IdCrosswalk::from_players(&[]).write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("player_id,full_name,espn_id,"));
+        assert_eq!(csv.lines().count(), 1);
+    }
+}
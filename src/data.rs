@@ -1,7 +1,9 @@
 use core::fmt;
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use thiserror::Error;
 
@@ -16,11 +18,12 @@ pub struct League {
     pub sport: String,
     pub shard: u32,
     pub settings: LeagueSettings,
-    pub season_type: String,
-    pub season: String,
+    pub season_type: SeasonType,
+    pub season: Season,
     pub scoring_settings: ScoringSettings,
     pub roster_positions: Vec<RosterPosition>,
-    pub previous_league_id: String,
+    /// The same league's previous season. Null (or "0") for a league's first season.
+    pub previous_league_id: Option<LeagueId>,
     pub name: String,
     pub metadata: Option<HashMap<String, Option<String>>>,
     pub loser_bracket_id: Option<u64>,
@@ -28,7 +31,7 @@ pub struct League {
     pub last_transation_id: Option<String>,
     pub last_read_id: Option<String>,
     pub last_pinned_message_id: Option<String>,
-    pub last_message_time: u64,
+    pub last_message_time: Timestamp,
     pub last_message_text_mape: Option<String>,
     pub last_message_id: Option<String>,
     pub last_message_attachment: Option<String>,
@@ -45,10 +48,12 @@ pub struct League {
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LeagueSettings {
+    /// 0 rolling waivers, 1 reverse standings, 2 FAAB. See `is_faab`.
     pub waiver_type: u8,
     pub waiver_day_of_week: u8,
     pub waiver_clear_days: u8,
-    pub waiver_budget: u8,
+    /// Each roster's FAAB budget for the season.
+    pub waiver_budget: u32,
     pub veto_votes_needed: u8,
     pub veto_show_votes: u8,
     pub veto_auto_poll: u8,
@@ -92,9 +97,21 @@ pub struct LeagueSettings {
     pub capacity_override: u8,
     pub best_ball: u8,
     pub bench_lock: u8,
+    #[serde(default)]
+    pub divisions: Option<u8>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+impl LeagueSettings {
+    /// Whether waiver claims are decided by free agent acquisition budget bids.
+    pub fn is_faab(&self) -> bool {
+        self.waiver_type == 2
+    }
+}
+
+/// Points awarded per unit of each stat. Keys missing from a league's settings
+/// are worth nothing; keys this struct does not name end up in `other`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
 pub struct ScoringSettings {
     pub st_ff: f64,
     pub pts_allow_7_13: f64,
@@ -140,19 +157,198 @@ pub struct ScoringSettings {
     pub pass_int: f64,
     pub fgm_40_49: f64,
     pub sack: f64,
+    #[serde(flatten)]
+    pub other: HashMap<String, f64>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+impl ScoringSettings {
+    /// Every stat key with its multiplier, including those in `other`.
+    pub fn to_map(&self) -> HashMap<String, f64> {
+        match serde_json::to_value(self) {
+            Ok(Value::Object(map)) => map
+                .into_iter()
+                .filter_map(|(key, value)| value.as_f64().map(|v| (key, v)))
+                .collect(),
+            _ => HashMap::new(),
+        }
+    }
+}
+
+/// A single player's stats for a week or season, keyed by the same stat names
+/// `ScoringSettings` uses (`pass_yd`, `rec`, `fgm_40_49`, `pts_allow`, ...).
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct StatLine(pub HashMap<String, f64>);
+
+impl StatLine {
+    /// The value of `stat`, or zero if the player did not record it.
+    pub fn get(&self, stat: &str) -> f64 {
+        self.0.get(stat).copied().unwrap_or(0.0)
+    }
+
+    pub fn contains(&self, stat: &str) -> bool {
+        self.0.contains_key(stat)
+    }
+
+    pub fn insert(&mut self, stat: &str, value: f64) {
+        self.0.insert(stat.to_string(), value);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.0.iter().map(|(stat, value)| (stat.as_str(), *value))
+    }
+
+    pub fn games_played(&self) -> f64 {
+        self.get("gp")
+    }
+
+    /// Sleeper's own standard scoring total, if it sent one.
+    pub fn pts_std(&self) -> Option<f64> {
+        self.0.get("pts_std").copied()
+    }
+
+    /// Sleeper's own half-PPR scoring total, if it sent one.
+    pub fn pts_half_ppr(&self) -> Option<f64> {
+        self.0.get("pts_half_ppr").copied()
+    }
+
+    /// Sleeper's own PPR scoring total, if it sent one.
+    pub fn pts_ppr(&self) -> Option<f64> {
+        self.0.get("pts_ppr").copied()
+    }
+}
+
+// Stat payloads occasionally carry nulls or non-numeric values, which are dropped.
+impl<'de> Deserialize<'de> for StatLine {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = HashMap::<String, Value>::deserialize(deserializer)?;
+        Ok(raw
+            .into_iter()
+            .filter_map(|(stat, value)| value.as_f64().map(|v| (stat, v)))
+            .collect())
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, f64)> for StatLine {
+    fn from_iter<I: IntoIterator<Item = (K, f64)>>(iter: I) -> Self {
+        StatLine(iter.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+}
+
+/// A lineup slot as listed in `League::roster_positions`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+#[allow(non_camel_case_types)]
 pub enum RosterPosition {
     QB,
     RB,
     WR,
     TE,
+    /// RB / WR / TE
     FLEX,
+    /// QB / RB / WR / TE
+    SUPER_FLEX,
+    /// WR / TE
+    REC_FLEX,
+    /// RB / WR
+    WRRB_FLEX,
     K,
     DEF,
-    BN,
+    DL,
+    LB,
+    DB,
+    /// DL / LB / DB
+    IDP_FLEX,
     IDP,
+    BN,
+    /// A slot this crate does not know about yet, kept verbatim.
+    Unknown(String),
+}
+
+impl RosterPosition {
+    /// The player positions which are allowed to fill this slot.
+    /// Bench slots accept anyone, unknown slots accept no one.
+    pub fn eligible_positions(&self) -> &'static [Position] {
+        const IDP: &[Position] = &[Position::DL, Position::LB, Position::DB];
+
+        match self {
+            RosterPosition::QB => &[Position::QB],
+            RosterPosition::RB => &[Position::RB],
+            RosterPosition::WR => &[Position::WR],
+            RosterPosition::TE => &[Position::TE],
+            RosterPosition::FLEX => &[Position::RB, Position::WR, Position::TE],
+            RosterPosition::SUPER_FLEX => &[Position::QB, Position::RB, Position::WR, Position::TE],
+            RosterPosition::REC_FLEX => &[Position::WR, Position::TE],
+            RosterPosition::WRRB_FLEX => &[Position::RB, Position::WR],
+            RosterPosition::K => &[Position::K],
+            RosterPosition::DEF => &[Position::DEF],
+            RosterPosition::DL => &[Position::DL],
+            RosterPosition::LB => &[Position::LB],
+            RosterPosition::DB => &[Position::DB],
+            RosterPosition::IDP_FLEX | RosterPosition::IDP => IDP,
+            RosterPosition::BN => &[
+                Position::QB,
+                Position::RB,
+                Position::WR,
+                Position::TE,
+                Position::K,
+                Position::DEF,
+                Position::DL,
+                Position::LB,
+                Position::DB,
+            ],
+            RosterPosition::Unknown(_) => &[],
+        }
+    }
+
+    /// Whether a player at `position` may be started in this slot.
+    pub fn is_eligible(&self, position: &Position) -> bool {
+        self.eligible_positions()
+            .contains(&position.fantasy_group())
+    }
+
+    /// True for every slot that counts towards the starting lineup.
+    pub fn is_starter(&self) -> bool {
+        !matches!(self, RosterPosition::BN)
+    }
+}
+
+impl FromStr for RosterPosition {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "QB" => Ok(RosterPosition::QB),
+            "RB" => Ok(RosterPosition::RB),
+            "WR" => Ok(RosterPosition::WR),
+            "TE" => Ok(RosterPosition::TE),
+            "FLEX" => Ok(RosterPosition::FLEX),
+            "SUPER_FLEX" => Ok(RosterPosition::SUPER_FLEX),
+            "REC_FLEX" => Ok(RosterPosition::REC_FLEX),
+            "WRRB_FLEX" => Ok(RosterPosition::WRRB_FLEX),
+            "K" => Ok(RosterPosition::K),
+            "DEF" => Ok(RosterPosition::DEF),
+            "DL" => Ok(RosterPosition::DL),
+            "LB" => Ok(RosterPosition::LB),
+            "DB" => Ok(RosterPosition::DB),
+            "IDP_FLEX" => Ok(RosterPosition::IDP_FLEX),
+            "IDP" => Ok(RosterPosition::IDP),
+            "BN" => Ok(RosterPosition::BN),
+            unmatched => Err(SleeperError::InvalidRosterPosition(unmatched.to_string())),
+        }
+    }
+}
+
+impl From<String> for RosterPosition {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(RosterPosition::Unknown(s))
+    }
+}
+
+impl From<RosterPosition> for String {
+    fn from(pos: RosterPosition) -> Self {
+        pos.to_string()
+    }
 }
 
 impl fmt::Display for RosterPosition {
@@ -163,10 +359,153 @@ impl fmt::Display for RosterPosition {
             RosterPosition::WR => write!(f, "WR"),
             RosterPosition::TE => write!(f, "TE"),
             RosterPosition::FLEX => write!(f, "FLEX"),
+            RosterPosition::SUPER_FLEX => write!(f, "SUPER_FLEX"),
+            RosterPosition::REC_FLEX => write!(f, "REC_FLEX"),
+            RosterPosition::WRRB_FLEX => write!(f, "WRRB_FLEX"),
             RosterPosition::K => write!(f, "K"),
             RosterPosition::DEF => write!(f, "DEF"),
-            RosterPosition::BN => write!(f, "BN"),
+            RosterPosition::DL => write!(f, "DL"),
+            RosterPosition::LB => write!(f, "LB"),
+            RosterPosition::DB => write!(f, "DB"),
+            RosterPosition::IDP_FLEX => write!(f, "IDP_FLEX"),
             RosterPosition::IDP => write!(f, "IDP"),
+            RosterPosition::BN => write!(f, "BN"),
+            RosterPosition::Unknown(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// A player's position. `NflPlayer::fantasy_positions` only ever holds the
+/// fantasy groupings (QB, RB, WR, TE, K, DEF, DL, LB, DB), while
+/// `NflPlayer::position` may be any of the more specific ones.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum Position {
+    QB,
+    RB,
+    FB,
+    WR,
+    TE,
+    K,
+    P,
+    DEF,
+    DL,
+    DE,
+    DT,
+    NT,
+    LB,
+    ILB,
+    OLB,
+    DB,
+    CB,
+    S,
+    SS,
+    FS,
+    OL,
+    OT,
+    OG,
+    T,
+    G,
+    C,
+    LS,
+    Unknown(String),
+}
+
+impl Position {
+    /// The fantasy grouping this position is scored and slotted as,
+    /// e.g. `DE` and `DT` are both `DL`.
+    pub fn fantasy_group(&self) -> Position {
+        match self {
+            Position::FB => Position::RB,
+            Position::DE | Position::DT | Position::NT => Position::DL,
+            Position::ILB | Position::OLB => Position::LB,
+            Position::CB | Position::S | Position::SS | Position::FS => Position::DB,
+            Position::OT | Position::OG | Position::T | Position::G | Position::C => Position::OL,
+            other => other.clone(),
+        }
+    }
+}
+
+impl FromStr for Position {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "QB" => Ok(Position::QB),
+            "RB" => Ok(Position::RB),
+            "FB" => Ok(Position::FB),
+            "WR" => Ok(Position::WR),
+            "TE" => Ok(Position::TE),
+            "K" => Ok(Position::K),
+            "P" => Ok(Position::P),
+            "DEF" => Ok(Position::DEF),
+            "DL" => Ok(Position::DL),
+            "DE" => Ok(Position::DE),
+            "DT" => Ok(Position::DT),
+            "NT" => Ok(Position::NT),
+            "LB" => Ok(Position::LB),
+            "ILB" => Ok(Position::ILB),
+            "OLB" => Ok(Position::OLB),
+            "DB" => Ok(Position::DB),
+            "CB" => Ok(Position::CB),
+            "S" => Ok(Position::S),
+            "SS" => Ok(Position::SS),
+            "FS" => Ok(Position::FS),
+            "OL" => Ok(Position::OL),
+            "OT" => Ok(Position::OT),
+            "OG" => Ok(Position::OG),
+            "T" => Ok(Position::T),
+            "G" => Ok(Position::G),
+            "C" => Ok(Position::C),
+            "LS" => Ok(Position::LS),
+            unmatched => Err(SleeperError::InvalidPosition(unmatched.to_string())),
+        }
+    }
+}
+
+impl From<String> for Position {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(Position::Unknown(s))
+    }
+}
+
+impl From<Position> for String {
+    fn from(pos: Position) -> Self {
+        pos.to_string()
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Position::QB => write!(f, "QB"),
+            Position::RB => write!(f, "RB"),
+            Position::FB => write!(f, "FB"),
+            Position::WR => write!(f, "WR"),
+            Position::TE => write!(f, "TE"),
+            Position::K => write!(f, "K"),
+            Position::P => write!(f, "P"),
+            Position::DEF => write!(f, "DEF"),
+            Position::DL => write!(f, "DL"),
+            Position::DE => write!(f, "DE"),
+            Position::DT => write!(f, "DT"),
+            Position::NT => write!(f, "NT"),
+            Position::LB => write!(f, "LB"),
+            Position::ILB => write!(f, "ILB"),
+            Position::OLB => write!(f, "OLB"),
+            Position::DB => write!(f, "DB"),
+            Position::CB => write!(f, "CB"),
+            Position::S => write!(f, "S"),
+            Position::SS => write!(f, "SS"),
+            Position::FS => write!(f, "FS"),
+            Position::OL => write!(f, "OL"),
+            Position::OT => write!(f, "OT"),
+            Position::OG => write!(f, "OG"),
+            Position::T => write!(f, "T"),
+            Position::G => write!(f, "G"),
+            Position::C => write!(f, "C"),
+            Position::LS => write!(f, "LS"),
+            Position::Unknown(s) => write!(f, "{}", s),
         }
     }
 }
@@ -175,19 +514,23 @@ impl fmt::Display for RosterPosition {
 pub struct Roster {
     pub taxi: Value,
     pub starters: Vec<String>,
-    pub settings: Value,
+    pub settings: RosterSettings,
     pub roster_id: u8,
     pub reserve: Value,
     pub players: Vec<String>,
     pub player_map: Value,
-    pub owner_id: OwnerId,
+    /// Null for rosters nobody currently owns.
+    pub owner_id: Option<OwnerId>,
     pub metadata: Value,
     pub league_id: LeagueId,
     pub keepers: Value,
     pub co_owners: Value,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+/// Season-to-date totals Sleeper keeps per roster. Points are split into a
+/// whole part (`fpts`) and hundredths (`fpts_decimal`); see `points_for`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
 pub struct RosterSettings {
     pub wins: u8,
     pub waiver_position: u16,
@@ -196,143 +539,1054 @@ pub struct RosterSettings {
     pub ties: u16,
     pub losses: u16,
     pub fpts: f64,
+    pub fpts_decimal: f64,
+    pub fpts_against: f64,
+    pub fpts_against_decimal: f64,
+    pub ppts: f64,
+    pub ppts_decimal: f64,
+    pub division: Option<u8>,
+}
+
+impl RosterSettings {
+    pub fn points_for(&self) -> f64 {
+        self.fpts + self.fpts_decimal / 100.0
+    }
+
+    pub fn points_against(&self) -> f64 {
+        self.fpts_against + self.fpts_against_decimal / 100.0
+    }
+
+    /// Points the roster would have scored with its optimal lineup every week.
+    pub fn potential_points(&self) -> f64 {
+        self.ppts + self.ppts_decimal / 100.0
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SleeperUser {
+    pub user_id: String,
+    pub username: Option<String>,
+    pub settings: Option<HashMap<String, String>>,
+    /// See `team_name` for the one field most callers want from here.
+    pub metadata: HashMap<String, Option<String>>,
+    pub is_owner: bool,
+    pub is_bot: bool,
+    pub league_id: LeagueId,
+    pub display_name: Option<String>,
+    pub avatar: Option<String>,
+}
+
+impl SleeperUser {
+    /// The team name the user chose for this league, if they set one.
+    pub fn team_name(&self) -> Option<&str> {
+        self.metadata
+            .get("team_name")?
+            .as_deref()
+            .filter(|name| !name.is_empty())
+    }
+}
+
+impl League {
+    /// The previous season's league ID, treating Sleeper's "0" placeholder as absent.
+    pub fn previous_league(&self) -> Option<&LeagueId> {
+        self.previous_league_id
+            .as_ref()
+            .filter(|id| !id.is_empty() && id.as_str() != "0")
+    }
+}
+
+/// One game of a playoff bracket, as returned by the winners and losers bracket endpoints.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct BracketMatchup {
+    #[serde(rename = "r")]
+    pub round: u8,
+    #[serde(rename = "m")]
+    pub matchup_id: u32,
+    #[serde(rename = "t1")]
+    pub team_1: Option<u8>,
+    #[serde(rename = "t2")]
+    pub team_2: Option<u8>,
+    #[serde(rename = "w")]
+    pub winner: Option<u8>,
+    #[serde(rename = "l")]
+    pub loser: Option<u8>,
+    #[serde(rename = "t1_from", default)]
+    pub team_1_from: Option<BracketSource>,
+    #[serde(rename = "t2_from", default)]
+    pub team_2_from: Option<BracketSource>,
+    /// Set on placement games: the winner finishes in this place, the loser in the next.
+    #[serde(rename = "p", default)]
+    pub place: Option<u8>,
+}
+
+/// Which earlier bracket game a team advanced from.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct BracketSource {
+    #[serde(rename = "w", default, skip_serializing_if = "Option::is_none")]
+    pub winner_of: Option<u32>,
+    #[serde(rename = "l", default, skip_serializing_if = "Option::is_none")]
+    pub loser_of: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Matchup {
+    pub starters_points: Vec<f64>,
+    pub starters: Vec<PlayerId>,
+    pub roster_id: u8,
+    pub points: f64,
+    pub players_points: Option<HashMap<PlayerId, f64>>,
+    pub players: Option<Vec<PlayerId>>,
+    /// The two rosters playing each other share a `matchup_id`.
+    /// It is null for rosters without a game that week (e.g. eliminated in the playoffs).
+    pub matchup_id: Option<u8>,
+    /// Set when the commissioner overrides `points` by hand.
+    pub custom_points: Option<f64>,
+}
+
+impl Matchup {
+    /// The points that count for this matchup, honouring commissioner overrides.
+    pub fn total_points(&self) -> f64 {
+        self.custom_points.unwrap_or(self.points)
+    }
+
+    /// Points `player_id` scored this week, if they were on the roster.
+    pub fn player_points(&self, player_id: &str) -> Option<f64> {
+        self.players_points.as_ref()?.get(player_id).copied()
+    }
+
+    pub fn has_player(&self, player_id: &str) -> bool {
+        self.players
+            .as_ref()
+            .is_some_and(|players| players.iter().any(|p| p == player_id))
+    }
+
+    pub fn is_starter(&self, player_id: &str) -> bool {
+        self.starters.iter().any(|p| p == player_id)
+    }
+}
+
+/// A roster move: a trade, waiver claim, free agent pickup or commissioner edit.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Transaction {
+    pub transaction_id: String,
+    #[serde(rename = "type")]
+    pub transaction_type: TransactionType,
+    pub status: TransactionStatus,
+    /// The week ("leg") the transaction was made in.
+    pub leg: u8,
+    pub created: Timestamp,
+    pub status_updated: Timestamp,
+    pub creator: Option<OwnerId>,
+    /// Every roster involved.
+    pub roster_ids: Vec<u8>,
+    #[serde(default)]
+    pub consenter_ids: Option<Vec<u8>>,
+    /// Player => roster which received them.
+    pub adds: Option<HashMap<PlayerId, u8>>,
+    /// Player => roster which gave them up.
+    pub drops: Option<HashMap<PlayerId, u8>>,
+    #[serde(default)]
+    pub draft_picks: Vec<TradedPick>,
+    /// FAAB sent along with a trade.
+    #[serde(default)]
+    pub waiver_budget: Vec<WaiverBudgetTransfer>,
+    pub settings: Option<TransactionSettings>,
+    /// Carries e.g. the reason a waiver claim failed, under "notes".
+    pub metadata: Option<HashMap<String, Option<String>>>,
+}
+
+impl Transaction {
+    pub fn is_complete(&self) -> bool {
+        self.status == TransactionStatus::Complete
+    }
+
+    /// Players `roster_id` received in this transaction.
+    pub fn added_to(&self, roster_id: u8) -> Vec<&PlayerId> {
+        Self::players_for(&self.adds, roster_id)
+    }
+
+    /// Players `roster_id` gave up in this transaction.
+    pub fn dropped_by(&self, roster_id: u8) -> Vec<&PlayerId> {
+        Self::players_for(&self.drops, roster_id)
+    }
+
+    fn players_for(moves: &Option<HashMap<PlayerId, u8>>, roster_id: u8) -> Vec<&PlayerId> {
+        let mut players: Vec<&PlayerId> = moves
+            .iter()
+            .flatten()
+            .filter(|(_, r)| **r == roster_id)
+            .map(|(player_id, _)| player_id)
+            .collect();
+        players.sort();
+        players
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum TransactionType {
+    Trade,
+    Waiver,
+    FreeAgent,
+    Commissioner,
+    Unknown(String),
+}
+
+impl FromStr for TransactionType {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "trade" => Ok(TransactionType::Trade),
+            "waiver" => Ok(TransactionType::Waiver),
+            "free_agent" => Ok(TransactionType::FreeAgent),
+            "commissioner" => Ok(TransactionType::Commissioner),
+            unmatched => Err(SleeperError::InvalidTransactionType(unmatched.to_string())),
+        }
+    }
+}
+
+impl From<String> for TransactionType {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(TransactionType::Unknown(s))
+    }
+}
+
+impl From<TransactionType> for String {
+    fn from(transaction_type: TransactionType) -> Self {
+        transaction_type.to_string()
+    }
+}
+
+impl fmt::Display for TransactionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransactionType::Trade => write!(f, "trade"),
+            TransactionType::Waiver => write!(f, "waiver"),
+            TransactionType::FreeAgent => write!(f, "free_agent"),
+            TransactionType::Commissioner => write!(f, "commissioner"),
+            TransactionType::Unknown(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum TransactionStatus {
+    Complete,
+    Failed,
+    Pending,
+    Unknown(String),
+}
+
+impl FromStr for TransactionStatus {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "complete" => Ok(TransactionStatus::Complete),
+            "failed" => Ok(TransactionStatus::Failed),
+            "pending" => Ok(TransactionStatus::Pending),
+            unmatched => Err(SleeperError::InvalidTransactionStatus(
+                unmatched.to_string(),
+            )),
+        }
+    }
+}
+
+impl From<String> for TransactionStatus {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(TransactionStatus::Unknown(s))
+    }
+}
+
+impl From<TransactionStatus> for String {
+    fn from(status: TransactionStatus) -> Self {
+        status.to_string()
+    }
+}
+
+impl fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransactionStatus::Complete => write!(f, "complete"),
+            TransactionStatus::Failed => write!(f, "failed"),
+            TransactionStatus::Pending => write!(f, "pending"),
+            TransactionStatus::Unknown(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// A draft pick changing hands. All three IDs are roster IDs.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TradedPick {
+    pub season: Season,
+    pub round: u8,
+    /// The roster the pick originally belonged to.
+    pub roster_id: u8,
+    pub previous_owner_id: Option<u8>,
+    pub owner_id: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct WaiverBudgetTransfer {
+    pub sender: u8,
+    pub receiver: u8,
+    pub amount: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TransactionSettings {
+    /// The FAAB bid of a waiver claim.
+    pub waiver_bid: Option<u32>,
+    /// Waiver priority at the time of the claim.
+    pub priority: Option<u16>,
+}
+
+/// A Sleeper account, as returned by the user lookup endpoint.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct User {
+    pub user_id: OwnerId,
+    pub username: Option<String>,
+    pub display_name: Option<String>,
+    pub avatar: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum DraftType {
+    Snake,
+    Linear,
+    Auction,
+    Unknown(String),
+}
+
+impl FromStr for DraftType {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "snake" => Ok(DraftType::Snake),
+            "linear" => Ok(DraftType::Linear),
+            "auction" => Ok(DraftType::Auction),
+            unmatched => Err(SleeperError::InvalidDraftType(unmatched.to_string())),
+        }
+    }
+}
+
+impl From<String> for DraftType {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(DraftType::Unknown(s))
+    }
+}
+
+impl From<DraftType> for String {
+    fn from(draft_type: DraftType) -> Self {
+        draft_type.to_string()
+    }
+}
+
+impl fmt::Display for DraftType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DraftType::Snake => write!(f, "snake"),
+            DraftType::Linear => write!(f, "linear"),
+            DraftType::Auction => write!(f, "auction"),
+            DraftType::Unknown(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum DraftStatus {
+    PreDraft,
+    Drafting,
+    Paused,
+    Complete,
+    Unknown(String),
+}
+
+impl FromStr for DraftStatus {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pre_draft" => Ok(DraftStatus::PreDraft),
+            "drafting" => Ok(DraftStatus::Drafting),
+            "paused" => Ok(DraftStatus::Paused),
+            "complete" => Ok(DraftStatus::Complete),
+            unmatched => Err(SleeperError::InvalidDraftStatus(unmatched.to_string())),
+        }
+    }
+}
+
+impl From<String> for DraftStatus {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(DraftStatus::Unknown(s))
+    }
+}
+
+impl From<DraftStatus> for String {
+    fn from(status: DraftStatus) -> Self {
+        status.to_string()
+    }
+}
+
+impl fmt::Display for DraftStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DraftStatus::PreDraft => write!(f, "pre_draft"),
+            DraftStatus::Drafting => write!(f, "drafting"),
+            DraftStatus::Paused => write!(f, "paused"),
+            DraftStatus::Complete => write!(f, "complete"),
+            DraftStatus::Unknown(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Draft {
+    pub draft_id: String,
+    pub league_id: Option<LeagueId>,
+    #[serde(rename = "type")]
+    pub draft_type: DraftType,
+    pub status: DraftStatus,
+    pub sport: String,
+    pub season: Season,
+    pub season_type: SeasonType,
+    pub start_time: Option<Timestamp>,
+    pub last_picked: Option<Timestamp>,
+    pub created: Option<Timestamp>,
+    /// Numeric settings such as "rounds" and "teams".
+    #[serde(default)]
+    pub settings: HashMap<String, Value>,
+    pub metadata: Option<HashMap<String, Option<String>>>,
+    /// User => draft slot.
+    pub draft_order: Option<HashMap<OwnerId, u8>>,
+    /// Draft slot => roster.
+    pub slot_to_roster_id: Option<HashMap<String, u8>>,
+}
+
+impl Draft {
+    pub fn is_complete(&self) -> bool {
+        self.status == DraftStatus::Complete
+    }
+
+    pub fn rounds(&self) -> Option<u64> {
+        self.settings.get("rounds")?.as_u64()
+    }
+
+    pub fn teams(&self) -> Option<u64> {
+        self.settings.get("teams")?.as_u64()
+    }
+
+    /// The roster picking from `draft_slot`.
+    pub fn roster_for_slot(&self, draft_slot: u8) -> Option<u8> {
+        self.slot_to_roster_id
+            .as_ref()?
+            .get(&draft_slot.to_string())
+            .copied()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DraftPick {
+    pub pick_no: u32,
+    pub round: u8,
+    pub draft_slot: u8,
+    pub player_id: PlayerId,
+    /// Empty for picks made by the auto-drafter.
+    pub picked_by: Option<OwnerId>,
+    /// Null in mock drafts.
+    #[serde(default, deserialize_with = "deserialize_optional_u8")]
+    pub roster_id: Option<u8>,
+    pub is_keeper: Option<bool>,
+    pub draft_id: String,
+    /// The player's name, position and team at the time of the pick; the
+    /// price paid for auction picks under "amount".
+    #[serde(default)]
+    pub metadata: HashMap<String, Option<String>>,
+}
+
+impl DraftPick {
+    fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .get(key)?
+            .as_deref()
+            .filter(|value| !value.is_empty())
+    }
+
+    pub fn player_name(&self) -> Option<String> {
+        match (self.metadata("first_name"), self.metadata("last_name")) {
+            (Some(first), Some(last)) => Some(format!("{} {}", first, last)),
+            (first, last) => first.or(last).map(str::to_string),
+        }
+    }
+
+    pub fn position(&self) -> Option<Position> {
+        self.metadata("position")?.parse().ok()
+    }
+
+    pub fn team(&self) -> Option<NflTeam> {
+        self.metadata("team")?.parse().ok()
+    }
+
+    /// What was paid for the player in an auction draft.
+    pub fn amount(&self) -> Option<u32> {
+        self.metadata("amount")?.parse().ok()
+    }
+}
+
+fn deserialize_optional_u8<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u8>, D::Error> {
+    match Option::<StringOrNumber>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(StringOrNumber::String(s)) => s.parse().map(Some).map_err(de::Error::custom),
+        Some(StringOrNumber::Number(n)) => u8::try_from(n).map(Some).map_err(de::Error::custom),
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum SleeperSport {
+    NFL,
+    NBA,
+    LCS,
+}
+
+/// A player's game designation. Sleeper sends `null` for healthy players,
+/// which deserializes to `InjuryStatus::Healthy`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum InjuryStatus {
+    #[default]
+    Healthy,
+    Questionable,
+    Doubtful,
+    Out,
+    InjuredReserve,
+    PUP,
+    Suspended,
+    Covid,
+    NotActive,
+    DidNotReport,
+    /// A designation this crate does not know about yet, kept verbatim.
+    Unknown(String),
+}
+
+impl FromStr for InjuryStatus {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<InjuryStatus, SleeperError> {
+        let lower = s.to_lowercase();
+        match lower.as_ref() {
+            "healthy" => Ok(InjuryStatus::Healthy),
+            "questionable" | "q" => Ok(InjuryStatus::Questionable),
+            "doubtful" | "d" => Ok(InjuryStatus::Doubtful),
+            "out" | "o" => Ok(InjuryStatus::Out),
+            "ir" => Ok(InjuryStatus::InjuredReserve),
+            "pup" => Ok(InjuryStatus::PUP),
+            "sus" => Ok(InjuryStatus::Suspended),
+            "cov" => Ok(InjuryStatus::Covid),
+            "na" => Ok(InjuryStatus::NotActive),
+            "dnr" => Ok(InjuryStatus::DidNotReport),
+            _ => Err(SleeperError::InvalidInjuryStatus(s.to_string())),
+        }
+    }
+}
+
+impl InjuryStatus {
+    pub fn from_json(node: &Value) -> Result<InjuryStatus, SleeperError> {
+        match node {
+            Value::String(s) => s.parse(),
+            Value::Null => Ok(InjuryStatus::Healthy),
+            _ => Err(SleeperError::InvalidInjuryStatus(
+                "{ an object }".to_string(),
+            )),
+        }
+    }
+
+    pub fn from_opt_string(s: Option<String>) -> Result<Self, SleeperError> {
+        match s {
+            Some(s) => s.parse(),
+            None => Ok(InjuryStatus::Healthy),
+        }
+    }
+
+    /// The code Sleeper uses for this designation, `None` for healthy players.
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            InjuryStatus::Healthy => None,
+            InjuryStatus::Questionable => Some("Questionable"),
+            InjuryStatus::Doubtful => Some("Doubtful"),
+            InjuryStatus::Out => Some("Out"),
+            InjuryStatus::InjuredReserve => Some("IR"),
+            InjuryStatus::PUP => Some("PUP"),
+            InjuryStatus::Suspended => Some("Sus"),
+            InjuryStatus::Covid => Some("COV"),
+            InjuryStatus::NotActive => Some("NA"),
+            InjuryStatus::DidNotReport => Some("DNR"),
+            InjuryStatus::Unknown(s) => Some(s),
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, InjuryStatus::Healthy)
+    }
+}
+
+impl Serialize for InjuryStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.code().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for InjuryStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match Option::<String>::deserialize(deserializer)? {
+            Some(s) => s.parse().unwrap_or(InjuryStatus::Unknown(s)),
+            None => InjuryStatus::Healthy,
+        })
+    }
+}
+
+impl fmt::Display for InjuryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InjuryStatus::Healthy => write!(f, "Healthy"),
+            InjuryStatus::Questionable => write!(f, "Questionable"),
+            InjuryStatus::Doubtful => write!(f, "Doubtful"),
+            InjuryStatus::Out => write!(f, "Out"),
+            InjuryStatus::InjuredReserve => write!(f, "Injured Reserve"),
+            InjuryStatus::PUP => write!(f, "PUP"),
+            InjuryStatus::Suspended => write!(f, "Suspended"),
+            InjuryStatus::Covid => write!(f, "COVID-19"),
+            InjuryStatus::NotActive => write!(f, "Inactive"),
+            InjuryStatus::DidNotReport => write!(f, "Did Not Report"),
+            InjuryStatus::Unknown(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// A player's roster status with their NFL team.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum PlayerStatus {
+    Active,
+    Inactive,
+    InjuredReserve,
+    PracticeSquad,
+    PhysicallyUnableToPerform,
+    NonFootballInjury,
+    Suspended,
+    Unknown(String),
+}
+
+impl FromStr for PlayerStatus {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Active" => Ok(PlayerStatus::Active),
+            "Inactive" => Ok(PlayerStatus::Inactive),
+            "Injured Reserve" => Ok(PlayerStatus::InjuredReserve),
+            "Practice Squad" => Ok(PlayerStatus::PracticeSquad),
+            "Physically Unable to Perform" => Ok(PlayerStatus::PhysicallyUnableToPerform),
+            "Non Football Injury" => Ok(PlayerStatus::NonFootballInjury),
+            "Reserve/Suspended" | "Suspended" => Ok(PlayerStatus::Suspended),
+            unmatched => Err(SleeperError::InvalidPlayerStatus(unmatched.to_string())),
+        }
+    }
+}
+
+impl From<String> for PlayerStatus {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(PlayerStatus::Unknown(s))
+    }
+}
+
+impl From<PlayerStatus> for String {
+    fn from(status: PlayerStatus) -> Self {
+        status.to_string()
+    }
+}
+
+impl fmt::Display for PlayerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlayerStatus::Active => write!(f, "Active"),
+            PlayerStatus::Inactive => write!(f, "Inactive"),
+            PlayerStatus::InjuredReserve => write!(f, "Injured Reserve"),
+            PlayerStatus::PracticeSquad => write!(f, "Practice Squad"),
+            PlayerStatus::PhysicallyUnableToPerform => write!(f, "Physically Unable to Perform"),
+            PlayerStatus::NonFootballInjury => write!(f, "Non Football Injury"),
+            PlayerStatus::Suspended => write!(f, "Reserve/Suspended"),
+            PlayerStatus::Unknown(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// How much a player took part in the latest practice.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum PracticeParticipation {
+    Full,
+    Limited,
+    DidNotParticipate,
+    Unknown(String),
+}
+
+impl FromStr for PracticeParticipation {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "full" | "fp" => Ok(PracticeParticipation::Full),
+            "limited" | "lp" => Ok(PracticeParticipation::Limited),
+            "dnp" | "did not participate" | "out" => Ok(PracticeParticipation::DidNotParticipate),
+            _ => Err(SleeperError::InvalidPracticeParticipation(s.to_string())),
+        }
+    }
+}
+
+impl From<String> for PracticeParticipation {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(PracticeParticipation::Unknown(s))
+    }
+}
+
+impl From<PracticeParticipation> for String {
+    fn from(participation: PracticeParticipation) -> Self {
+        participation.to_string()
+    }
+}
+
+impl fmt::Display for PracticeParticipation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PracticeParticipation::Full => write!(f, "Full"),
+            PracticeParticipation::Limited => write!(f, "Limited"),
+            PracticeParticipation::DidNotParticipate => write!(f, "DNP"),
+            PracticeParticipation::Unknown(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl FromStr for SleeperSport {
+    type Err = SleeperError;
+
+    fn from_str(str: &str) -> Result<SleeperSport, SleeperError> {
+        match str.to_lowercase().as_str() {
+            "nfl" => Ok(SleeperSport::NFL),
+            "nba" => Ok(SleeperSport::NBA),
+            "lcs" => Ok(SleeperSport::LCS),
+            unmatched => Err(SleeperError::InvalidSport(unmatched.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for SleeperSport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SleeperSport::NFL => write!(f, "nfl"),
+            SleeperSport::NBA => write!(f, "nba"),
+            SleeperSport::LCS => write!(f, "lcs"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SportState {
+    pub week: u8,
+    pub season_type: SeasonType,
+    pub season_start_date: Option<Date>,
+    pub season: Season,
+    pub previous_season: Season,
+    pub leg: u8,
+    pub league_season: Season,
+    pub league_create_season: Season,
+    pub display_week: u8,
+}
+
+/// An NFL season, identified by the year it kicks off in.
+/// Sleeper sends seasons as strings (`"2023"`), which is also how they serialize.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Season(pub u16);
+
+impl Season {
+    pub fn year(&self) -> u16 {
+        self.0
+    }
+
+    pub fn previous(&self) -> Option<Season> {
+        self.0.checked_sub(1).map(Season)
+    }
+
+    pub fn next(&self) -> Option<Season> {
+        self.0.checked_add(1).map(Season)
+    }
+}
+
+impl FromStr for Season {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().parse::<u16>() {
+            Ok(year) if s.trim().len() == 4 => Ok(Season(year)),
+            _ => Err(SleeperError::InvalidSeason(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Season {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for Season {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Season {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match StringOrNumber::deserialize(deserializer)? {
+            StringOrNumber::String(s) => s.parse().map_err(de::Error::custom),
+            StringOrNumber::Number(n) => u16::try_from(n)
+                .map(Season)
+                .map_err(|_| de::Error::custom(SleeperError::InvalidSeason(n.to_string()))),
+        }
+    }
+}
+
+/// Sleeper is inconsistent about quoting some numeric values.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Number(u64),
+}
+
+/// The part of the year a season (or league) is in.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum SeasonType {
+    Pre,
+    Regular,
+    Post,
+    Off,
+    Unknown(String),
+}
+
+impl FromStr for SeasonType {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pre" => Ok(SeasonType::Pre),
+            "regular" => Ok(SeasonType::Regular),
+            "post" => Ok(SeasonType::Post),
+            "off" => Ok(SeasonType::Off),
+            _ => Err(SleeperError::InvalidSeasonType(s.to_string())),
+        }
+    }
+}
+
+impl From<String> for SeasonType {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(SeasonType::Unknown(s))
+    }
+}
+
+impl From<SeasonType> for String {
+    fn from(season_type: SeasonType) -> Self {
+        season_type.to_string()
+    }
+}
+
+impl fmt::Display for SeasonType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeasonType::Pre => write!(f, "pre"),
+            SeasonType::Regular => write!(f, "regular"),
+            SeasonType::Post => write!(f, "post"),
+            SeasonType::Off => write!(f, "off"),
+            SeasonType::Unknown(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// A calendar date, sent by Sleeper in the format `YYYY-MM-DD`.
+/// Enable the `chrono` feature to convert to and from `chrono::NaiveDate`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Returns `None` if the day does not exist in the given month.
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Date> {
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if year.is_multiple_of(4)
+                && (!year.is_multiple_of(100) || year.is_multiple_of(400)) =>
+            {
+                29
+            }
+            2 => 28,
+            _ => return None,
+        };
+
+        if day == 0 || day > days_in_month {
+            return None;
+        }
+
+        Some(Date { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl FromStr for Date {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SleeperError::InvalidDate(s.to_string());
+        let mut parts = s.trim().splitn(3, '-');
+
+        let mut next = || -> Result<&str, SleeperError> { parts.next().ok_or_else(invalid) };
+        let year = next()?.parse().map_err(|_| invalid())?;
+        let month = next()?.parse().map_err(|_| invalid())?;
+        let day = next()?.parse().map_err(|_| invalid())?;
+
+        Date::new(year, month, day).ok_or_else(invalid)
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct SleeperUser {
-    pub user_id: String,
-    pub username: Option<String>,
-    pub settings: Option<HashMap<String, String>>,
-    // TODO - metadata has one important field "team_name" that should be communicated
-    pub metadata: HashMap<String, Option<String>>,
-    pub is_owner: bool,
-    pub is_bot: bool,
-    pub league_id: LeagueId,
-    pub display_name: Option<String>,
-    pub avatar: Option<String>,
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct Matchup {
-    starters_points: Vec<f64>,
-    starters: Vec<PlayerId>,
-    roster_id: u8,
-    points: f64,
-    players_points: Option<HashMap<PlayerId, f64>>,
-    players: Option<Vec<PlayerId>>,
-    matchup_id: u8,
-    custom_points: Option<HashMap<String, String>>,
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub enum SleeperSport {
-    NFL,
-    NBA,
-    LCS,
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub enum InjuryStatus {
-    Healthy,
-    InjuredReserve,
-    Covid,
-    NotActive,
-    Out,
-    PUP,
-    Questionable,
-    Suspended,
+#[cfg(feature = "chrono")]
+impl From<Date> for chrono::NaiveDate {
+    fn from(date: Date) -> Self {
+        chrono::NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+            .expect("Date is always a valid calendar date")
+    }
 }
 
-impl InjuryStatus {
-    pub fn from_str(s: &str) -> Result<InjuryStatus, SleeperError> {
-        let lower = s.to_lowercase();
-        match lower.as_ref() {
-            "questionable" => Ok(InjuryStatus::Questionable),
-            "cov" => Ok(InjuryStatus::Covid),
-            "ir" => Ok(InjuryStatus::InjuredReserve),
-            "na" => Ok(InjuryStatus::NotActive),
-            "pup" => Ok(InjuryStatus::PUP),
-            "sus" => Ok(InjuryStatus::Suspended),
-            unmatched => Err(SleeperError::InvalidInjuryStatus(unmatched.to_string())),
-        }
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDate> for Date {
+    type Error = SleeperError;
+
+    fn try_from(date: chrono::NaiveDate) -> Result<Self, Self::Error> {
+        use chrono::Datelike;
+
+        u16::try_from(date.year())
+            .ok()
+            .and_then(|year| Date::new(year, date.month() as u8, date.day() as u8))
+            .ok_or_else(|| SleeperError::InvalidDate(date.to_string()))
     }
+}
 
-    pub fn from_json(node: &Value) -> Result<InjuryStatus, SleeperError> {
-        match node {
-            Value::String(s) => InjuryStatus::from_str(s),
-            Value::Null => Ok(InjuryStatus::Healthy),
-            _ => Err(SleeperError::InvalidInjuryStatus(
-                "{ an object }".to_string(),
-            )),
-        }
+/// A point in time, sent by Sleeper as milliseconds since the Unix epoch.
+/// Enable the `chrono` feature to convert to `chrono::DateTime<Utc>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Timestamp(pub u64);
+
+impl Timestamp {
+    pub fn now() -> Timestamp {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        Timestamp(since_epoch.as_millis() as u64)
     }
 
-    pub fn from_opt_string(s: Option<String>) -> Result<Self, SleeperError> {
-        match s {
-            Some(s) => InjuryStatus::from_str(s.as_ref()),
-            None => Ok(InjuryStatus::Healthy),
-        }
+    pub fn as_millis(&self) -> u64 {
+        self.0
     }
-}
 
-impl fmt::Display for InjuryStatus {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            InjuryStatus::Healthy => write!(f, "Healthy"),
-            InjuryStatus::InjuredReserve => write!(f, "Injured Reserve"),
-            InjuryStatus::Covid => write!(f, "COVID-19"),
-            InjuryStatus::NotActive => write!(f, "Inactive"),
-            InjuryStatus::Out => write!(f, "Out"),
-            InjuryStatus::PUP => write!(f, "PUP"),
-            InjuryStatus::Questionable => write!(f, "Questionable"),
-            InjuryStatus::Suspended => write!(f, "Suspended"),
-        }
+    pub fn to_system_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.0)
     }
-}
 
-impl SleeperSport {
-    pub fn from_str(str: &str) -> Result<SleeperSport, SleeperError> {
-        match str.to_lowercase().as_str() {
-            "nfl" => Ok(SleeperSport::NFL),
-            "nba" => Ok(SleeperSport::NBA),
-            "lcs" => Ok(SleeperSport::LCS),
-            unmatched => Err(SleeperError::InvalidSport(unmatched.to_string())),
-        }
+    /// Returns `None` if the timestamp is outside of chrono's range.
+    #[cfg(feature = "chrono")]
+    pub fn to_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        i64::try_from(self.0)
+            .ok()
+            .and_then(chrono::DateTime::from_timestamp_millis)
     }
 
-    pub fn to_string(&self) -> String {
-        match self {
-            SleeperSport::NFL => String::from("nfl"),
-            SleeperSport::NBA => String::from("nba"),
-            SleeperSport::LCS => String::from("lcs"),
-        }
+    /// The UTC calendar date this timestamp falls on.
+    #[cfg(feature = "chrono")]
+    pub fn date(&self) -> Option<Date> {
+        let date = self.to_datetime()?.date_naive();
+        Date::try_from(date).ok()
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct SportState {
-    pub week: u8,
-    pub season_type: String,
-    pub season_start_date: String, // TODO this is a date in fmt YYYY-MM-DD
-    pub season: String,            // TODO this is year in fmt YYYY
-    pub previous_season: String,   // TODO this is year in fmt YYYY
-    pub leg: u8,
-    pub league_season: String,
-    pub league_create_season: String,
-    pub display_week: u8,
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        Timestamp(since_epoch.as_millis() as u64)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    fn from(time: chrono::DateTime<chrono::Utc>) -> Self {
+        Timestamp(time.timestamp_millis().max(0) as u64)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NflPlayer {
     pub birth_city: Option<String>,
-    pub status: Option<String>,
+    pub status: Option<PlayerStatus>,
     pub sportradar_id: Option<String>, // UUID
     pub search_first_name: Option<String>,
     pub practice_description: Option<String>,
     pub injury_body_part: Option<String>,
-    pub injury_status: Option<String>,
-    pub team: Option<String>,
-    pub position: Option<String>,
+    #[serde(default)]
+    pub injury_status: InjuryStatus,
+    #[serde(default)]
+    pub team: Option<NflTeam>,
+    pub position: Option<Position>,
     pub metadata: Option<HashMap<String, Value>>,
     pub first_name: String,
     pub injury_start_date: Option<String>,
@@ -343,17 +1597,17 @@ pub struct NflPlayer {
     pub age: Option<u8>,
     pub pandascore_id: Option<String>,
     pub sport: String,
-    pub practice_participation: Option<String>,
+    pub practice_participation: Option<PracticeParticipation>,
     pub weight: Option<String>,
     pub swish_id: Option<u32>,
     pub depth_chart_order: Option<u8>,
     pub years_exp: Option<i8>,
     pub college: Option<String>,
-    pub news_updated: Option<u64>,
+    pub news_updated: Option<Timestamp>,
     pub rotoworld_id: Option<u64>,
     pub last_name: String,
     pub birth_state: Option<String>,
-    pub fantasy_positions: Option<Vec<String>>,
+    pub fantasy_positions: Option<Vec<Position>>,
     pub birth_country: Option<String>,
     pub search_full_name: Option<String>,
     pub stats_id: Option<u32>,
@@ -372,6 +1626,393 @@ pub struct NflPlayer {
     pub height: Option<String>,
 }
 
+impl NflPlayer {
+    /// The team this player is the defense of, if it is a team defense.
+    pub fn defense_team(&self) -> Option<NflTeam> {
+        match self.position {
+            Some(Position::DEF) => NflTeam::from_defense_player_id(&self.player_id),
+            _ => None,
+        }
+    }
+
+    /// Height in inches. Sleeper sends either total inches (`"74"`)
+    /// or feet and inches (`"6'2\""`).
+    pub fn height_inches(&self) -> Option<u8> {
+        let height = self.height.as_deref()?.trim();
+
+        match height.split_once('\'') {
+            Some((feet, inches)) => {
+                let feet: u8 = feet.trim().parse().ok()?;
+                let inches = inches.trim().trim_end_matches('"').trim();
+                let inches: u8 = if inches.is_empty() {
+                    0
+                } else {
+                    inches.parse().ok()?
+                };
+                feet.checked_mul(12)?.checked_add(inches)
+            }
+            None => height.parse().ok(),
+        }
+    }
+
+    /// Weight in pounds.
+    pub fn weight_lbs(&self) -> Option<u16> {
+        let weight = self.weight.as_deref()?.trim();
+        weight.trim_end_matches("lbs").trim().parse().ok()
+    }
+
+    pub fn birth_date(&self) -> Option<Date> {
+        self.birth_date.as_deref()?.parse().ok()
+    }
+
+    /// The player's age in whole years as of `date`. Unlike `age`, which
+    /// Sleeper only refreshes occasionally, this is computed from `birth_date`.
+    pub fn age_on(&self, date: Date) -> Option<u8> {
+        let born = self.birth_date()?;
+        if date < born {
+            return None;
+        }
+
+        let had_birthday = (date.month(), date.day()) >= (born.month(), born.day());
+        let years = date.year() - born.year() - u16::from(!had_birthday);
+        u8::try_from(years).ok()
+    }
+
+    /// The season the player entered the league, from `metadata.rookie_year`.
+    pub fn rookie_year(&self) -> Option<Season> {
+        match self.metadata.as_ref()?.get("rookie_year")? {
+            Value::String(year) => year.parse().ok(),
+            Value::Number(year) => year
+                .as_u64()
+                .and_then(|y| u16::try_from(y).ok())
+                .map(Season),
+            _ => None,
+        }
+    }
+}
+
+/// One of the 32 NFL franchises. Team abbreviations are also the `PlayerId`s
+/// Sleeper uses for team defenses.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum NflTeam {
+    ARI,
+    ATL,
+    BAL,
+    BUF,
+    CAR,
+    CHI,
+    CIN,
+    CLE,
+    DAL,
+    DEN,
+    DET,
+    GB,
+    HOU,
+    IND,
+    JAX,
+    KC,
+    LAC,
+    LAR,
+    LV,
+    MIA,
+    MIN,
+    NE,
+    NO,
+    NYG,
+    NYJ,
+    PHI,
+    PIT,
+    SEA,
+    SF,
+    TB,
+    TEN,
+    WAS,
+    /// An abbreviation this crate does not know about yet, kept verbatim.
+    Unknown(String),
+}
+
+impl NflTeam {
+    pub const ALL: [NflTeam; 32] = [
+        NflTeam::ARI,
+        NflTeam::ATL,
+        NflTeam::BAL,
+        NflTeam::BUF,
+        NflTeam::CAR,
+        NflTeam::CHI,
+        NflTeam::CIN,
+        NflTeam::CLE,
+        NflTeam::DAL,
+        NflTeam::DEN,
+        NflTeam::DET,
+        NflTeam::GB,
+        NflTeam::HOU,
+        NflTeam::IND,
+        NflTeam::JAX,
+        NflTeam::KC,
+        NflTeam::LAC,
+        NflTeam::LAR,
+        NflTeam::LV,
+        NflTeam::MIA,
+        NflTeam::MIN,
+        NflTeam::NE,
+        NflTeam::NO,
+        NflTeam::NYG,
+        NflTeam::NYJ,
+        NflTeam::PHI,
+        NflTeam::PIT,
+        NflTeam::SEA,
+        NflTeam::SF,
+        NflTeam::TB,
+        NflTeam::TEN,
+        NflTeam::WAS,
+    ];
+
+    /// The abbreviation Sleeper uses for this team.
+    pub fn abbreviation(&self) -> &str {
+        match self {
+            NflTeam::ARI => "ARI",
+            NflTeam::ATL => "ATL",
+            NflTeam::BAL => "BAL",
+            NflTeam::BUF => "BUF",
+            NflTeam::CAR => "CAR",
+            NflTeam::CHI => "CHI",
+            NflTeam::CIN => "CIN",
+            NflTeam::CLE => "CLE",
+            NflTeam::DAL => "DAL",
+            NflTeam::DEN => "DEN",
+            NflTeam::DET => "DET",
+            NflTeam::GB => "GB",
+            NflTeam::HOU => "HOU",
+            NflTeam::IND => "IND",
+            NflTeam::JAX => "JAX",
+            NflTeam::KC => "KC",
+            NflTeam::LAC => "LAC",
+            NflTeam::LAR => "LAR",
+            NflTeam::LV => "LV",
+            NflTeam::MIA => "MIA",
+            NflTeam::MIN => "MIN",
+            NflTeam::NE => "NE",
+            NflTeam::NO => "NO",
+            NflTeam::NYG => "NYG",
+            NflTeam::NYJ => "NYJ",
+            NflTeam::PHI => "PHI",
+            NflTeam::PIT => "PIT",
+            NflTeam::SEA => "SEA",
+            NflTeam::SF => "SF",
+            NflTeam::TB => "TB",
+            NflTeam::TEN => "TEN",
+            NflTeam::WAS => "WAS",
+            NflTeam::Unknown(s) => s,
+        }
+    }
+
+    /// `None` for a team this crate does not know about.
+    pub fn name(&self) -> Option<&'static str> {
+        let name = match self {
+            NflTeam::ARI => "Arizona Cardinals",
+            NflTeam::ATL => "Atlanta Falcons",
+            NflTeam::BAL => "Baltimore Ravens",
+            NflTeam::BUF => "Buffalo Bills",
+            NflTeam::CAR => "Carolina Panthers",
+            NflTeam::CHI => "Chicago Bears",
+            NflTeam::CIN => "Cincinnati Bengals",
+            NflTeam::CLE => "Cleveland Browns",
+            NflTeam::DAL => "Dallas Cowboys",
+            NflTeam::DEN => "Denver Broncos",
+            NflTeam::DET => "Detroit Lions",
+            NflTeam::GB => "Green Bay Packers",
+            NflTeam::HOU => "Houston Texans",
+            NflTeam::IND => "Indianapolis Colts",
+            NflTeam::JAX => "Jacksonville Jaguars",
+            NflTeam::KC => "Kansas City Chiefs",
+            NflTeam::LAC => "Los Angeles Chargers",
+            NflTeam::LAR => "Los Angeles Rams",
+            NflTeam::LV => "Las Vegas Raiders",
+            NflTeam::MIA => "Miami Dolphins",
+            NflTeam::MIN => "Minnesota Vikings",
+            NflTeam::NE => "New England Patriots",
+            NflTeam::NO => "New Orleans Saints",
+            NflTeam::NYG => "New York Giants",
+            NflTeam::NYJ => "New York Jets",
+            NflTeam::PHI => "Philadelphia Eagles",
+            NflTeam::PIT => "Pittsburgh Steelers",
+            NflTeam::SEA => "Seattle Seahawks",
+            NflTeam::SF => "San Francisco 49ers",
+            NflTeam::TB => "Tampa Bay Buccaneers",
+            NflTeam::TEN => "Tennessee Titans",
+            NflTeam::WAS => "Washington Commanders",
+            NflTeam::Unknown(_) => return None,
+        };
+
+        Some(name)
+    }
+
+    /// `None` for a team this crate does not know about.
+    pub fn division(&self) -> Option<Division> {
+        let division = match self {
+            NflTeam::BUF | NflTeam::MIA | NflTeam::NE | NflTeam::NYJ => Division::AfcEast,
+            NflTeam::BAL | NflTeam::CIN | NflTeam::CLE | NflTeam::PIT => Division::AfcNorth,
+            NflTeam::HOU | NflTeam::IND | NflTeam::JAX | NflTeam::TEN => Division::AfcSouth,
+            NflTeam::DEN | NflTeam::KC | NflTeam::LAC | NflTeam::LV => Division::AfcWest,
+            NflTeam::DAL | NflTeam::NYG | NflTeam::PHI | NflTeam::WAS => Division::NfcEast,
+            NflTeam::CHI | NflTeam::DET | NflTeam::GB | NflTeam::MIN => Division::NfcNorth,
+            NflTeam::ATL | NflTeam::CAR | NflTeam::NO | NflTeam::TB => Division::NfcSouth,
+            NflTeam::ARI | NflTeam::LAR | NflTeam::SEA | NflTeam::SF => Division::NfcWest,
+            NflTeam::Unknown(_) => return None,
+        };
+
+        Some(division)
+    }
+
+    pub fn conference(&self) -> Option<Conference> {
+        self.division().map(|division| division.conference())
+    }
+
+    /// Abbreviations this franchise has gone by, either before relocating or
+    /// with other data providers. All of them are accepted by `from_str`.
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            NflTeam::ARI => &["ARZ"],
+            NflTeam::GB => &["GNB"],
+            NflTeam::JAX => &["JAC"],
+            NflTeam::KC => &["KAN"],
+            NflTeam::LAC => &["SD", "SDG"],
+            NflTeam::LAR => &["LA", "STL"],
+            NflTeam::LV => &["LVR", "OAK"],
+            NflTeam::NE => &["NWE"],
+            NflTeam::NO => &["NOR"],
+            NflTeam::SF => &["SFO"],
+            NflTeam::TB => &["TAM"],
+            NflTeam::WAS => &["WSH"],
+            _ => &[],
+        }
+    }
+
+    /// The `PlayerId` of this team's defense.
+    pub fn defense_player_id(&self) -> PlayerId {
+        self.abbreviation().to_string()
+    }
+
+    /// Resolve a team defense's `PlayerId` (e.g. `"KC"`) to its team.
+    /// Returns `None` for the numeric IDs of individual players.
+    pub fn from_defense_player_id(player_id: &str) -> Option<NflTeam> {
+        player_id.parse().ok()
+    }
+}
+
+impl FromStr for NflTeam {
+    type Err = SleeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "ARI" | "ARZ" => Ok(NflTeam::ARI),
+            "ATL" => Ok(NflTeam::ATL),
+            "BAL" => Ok(NflTeam::BAL),
+            "BUF" => Ok(NflTeam::BUF),
+            "CAR" => Ok(NflTeam::CAR),
+            "CHI" => Ok(NflTeam::CHI),
+            "CIN" => Ok(NflTeam::CIN),
+            "CLE" => Ok(NflTeam::CLE),
+            "DAL" => Ok(NflTeam::DAL),
+            "DEN" => Ok(NflTeam::DEN),
+            "DET" => Ok(NflTeam::DET),
+            "GB" | "GNB" => Ok(NflTeam::GB),
+            "HOU" => Ok(NflTeam::HOU),
+            "IND" => Ok(NflTeam::IND),
+            "JAX" | "JAC" => Ok(NflTeam::JAX),
+            "KC" | "KAN" => Ok(NflTeam::KC),
+            "LAC" | "SD" | "SDG" => Ok(NflTeam::LAC),
+            "LAR" | "LA" | "STL" => Ok(NflTeam::LAR),
+            "LV" | "LVR" | "OAK" => Ok(NflTeam::LV),
+            "MIA" => Ok(NflTeam::MIA),
+            "MIN" => Ok(NflTeam::MIN),
+            "NE" | "NWE" => Ok(NflTeam::NE),
+            "NO" | "NOR" => Ok(NflTeam::NO),
+            "NYG" => Ok(NflTeam::NYG),
+            "NYJ" => Ok(NflTeam::NYJ),
+            "PHI" => Ok(NflTeam::PHI),
+            "PIT" => Ok(NflTeam::PIT),
+            "SEA" => Ok(NflTeam::SEA),
+            "SF" | "SFO" => Ok(NflTeam::SF),
+            "TB" | "TAM" => Ok(NflTeam::TB),
+            "TEN" => Ok(NflTeam::TEN),
+            "WAS" | "WSH" => Ok(NflTeam::WAS),
+            _ => Err(SleeperError::InvalidTeam(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for NflTeam {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.abbreviation())
+    }
+}
+
+impl From<String> for NflTeam {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(NflTeam::Unknown(s))
+    }
+}
+
+impl From<NflTeam> for String {
+    fn from(team: NflTeam) -> Self {
+        team.to_string()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Conference {
+    AFC,
+    NFC,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Division {
+    AfcEast,
+    AfcNorth,
+    AfcSouth,
+    AfcWest,
+    NfcEast,
+    NfcNorth,
+    NfcSouth,
+    NfcWest,
+}
+
+impl Division {
+    pub fn conference(&self) -> Conference {
+        match self {
+            Division::AfcEast | Division::AfcNorth | Division::AfcSouth | Division::AfcWest => {
+                Conference::AFC
+            }
+            Division::NfcEast | Division::NfcNorth | Division::NfcSouth | Division::NfcWest => {
+                Conference::NFC
+            }
+        }
+    }
+
+    pub fn teams(&self) -> impl Iterator<Item = NflTeam> + '_ {
+        NflTeam::ALL
+            .into_iter()
+            .filter(move |team| team.division() == Some(*self))
+    }
+}
+
+impl fmt::Display for Division {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Division::AfcEast => write!(f, "AFC East"),
+            Division::AfcNorth => write!(f, "AFC North"),
+            Division::AfcSouth => write!(f, "AFC South"),
+            Division::AfcWest => write!(f, "AFC West"),
+            Division::NfcEast => write!(f, "NFC East"),
+            Division::NfcNorth => write!(f, "NFC North"),
+            Division::NfcSouth => write!(f, "NFC South"),
+            Division::NfcWest => write!(f, "NFC West"),
+        }
+    }
+}
+
 pub enum AllPlayers {
     NFL(HashMap<PlayerId, NflPlayer>),
     // TODO
@@ -393,6 +2034,61 @@ pub enum SleeperError {
 
     #[error("could parse String into PlayerStatus: \"{0}\" was not a valid injury designation")]
     InvalidInjuryStatus(String),
+
+    #[error("could not parse String into PlayerStatus: \"{0}\" was not a valid roster status")]
+    InvalidPlayerStatus(String),
+
+    #[error("could not parse String into PracticeParticipation: \"{0}\" was not a valid practice report")]
+    InvalidPracticeParticipation(String),
+
+    #[error("could not parse String into Season: \"{0}\" was not a valid year")]
+    InvalidSeason(String),
+
+    #[error("could not parse String into SeasonType: \"{0}\" was not a valid season type")]
+    InvalidSeasonType(String),
+
+    #[error("could not parse String into Date: \"{0}\" was not a valid YYYY-MM-DD date")]
+    InvalidDate(String),
+
+    #[error("could not parse String into NflTeam: \"{0}\" was not a valid team abbreviation")]
+    InvalidTeam(String),
+
+    #[error("could not write export: {0}")]
+    ExportError(String),
+
+    #[error("could not parse String into RosterPosition: \"{0}\" was not a valid roster slot")]
+    InvalidRosterPosition(String),
+
+    #[error("could not parse String into Position: \"{0}\" was not a valid player position")]
+    InvalidPosition(String),
+
+    #[error(
+        "could not parse String into TransactionType: \"{0}\" was not a valid transaction type"
+    )]
+    InvalidTransactionType(String),
+
+    #[error(
+        "could not parse String into TransactionStatus: \"{0}\" was not a valid transaction status"
+    )]
+    InvalidTransactionStatus(String),
+
+    #[error("database error: {0}")]
+    DatabaseError(String),
+
+    #[error("could not read or write file: {0}")]
+    IoError(String),
+
+    #[error("no Sleeper user found for \"{0}\"")]
+    UserNotFound(String),
+
+    #[error("could not parse String into DraftType: \"{0}\" was not a valid draft type")]
+    InvalidDraftType(String),
+
+    #[error("could not parse String into DraftStatus: \"{0}\" was not a valid draft status")]
+    InvalidDraftStatus(String),
+
+    #[error("could not start the async runtime: {0}")]
+    RuntimeError(String),
 }
 
 #[cfg(test)]
@@ -552,7 +2248,7 @@ mod tests {
         }"#;
 
         let result: Result<League, _> = serde_json::from_str(json_str);
-        assert_eq!(result.is_ok(), true);
+        assert!(result.is_ok());
         let league = result.unwrap();
         assert_eq!(league.name, "League name");
         assert_eq!(league.status, "complete");
@@ -575,8 +2271,9 @@ mod tests {
         assert_eq!(league.avatar, "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
         assert_eq!(league.company_id, None);
         assert_eq!(league.shard, 434);
-        assert_eq!(league.season, "2023");
-        assert_eq!(league.season_type, "regular");
+        assert_eq!(league.season, Season(2023));
+        assert_eq!(league.season_type, SeasonType::Regular);
+        assert_eq!(league.last_message_time, Timestamp(1704267066920));
         assert_eq!(league.sport, "nfl");
         assert_eq!(league.scoring_settings.sack, 1.0);
         assert_eq!(league.scoring_settings.fgm_40_49, 4.0);
@@ -597,6 +2294,294 @@ mod tests {
         let json_str = r#"{ "invalid_field": null }"#;
 
         let result: Result<League, _> = serde_json::from_str(json_str);
-        assert_eq!(result.is_err(), true);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_roster_positions_with_flex_and_idp_slots() {
+        let json_str = r#"["QB", "SUPER_FLEX", "REC_FLEX", "WRRB_FLEX", "DL", "LB", "DB", "IDP_FLEX", "BN", "NEW_SLOT"]"#;
+
+        let positions: Vec<RosterPosition> = serde_json::from_str(json_str).unwrap();
+        assert_eq!(
+            positions,
+            vec![
+                RosterPosition::QB,
+                RosterPosition::SUPER_FLEX,
+                RosterPosition::REC_FLEX,
+                RosterPosition::WRRB_FLEX,
+                RosterPosition::DL,
+                RosterPosition::LB,
+                RosterPosition::DB,
+                RosterPosition::IDP_FLEX,
+                RosterPosition::BN,
+                RosterPosition::Unknown("NEW_SLOT".to_string()),
+            ]
+        );
+
+        let round_trip = serde_json::to_string(&positions).unwrap();
+        assert_eq!(round_trip, json_str.replace(", ", ","));
+    }
+
+    #[test]
+    fn test_roster_position_eligible_positions() {
+        assert!(RosterPosition::SUPER_FLEX.is_eligible(&Position::QB));
+        assert!(!RosterPosition::FLEX.is_eligible(&Position::QB));
+        assert!(RosterPosition::REC_FLEX.is_eligible(&Position::TE));
+        assert!(!RosterPosition::REC_FLEX.is_eligible(&Position::RB));
+        assert!(RosterPosition::WRRB_FLEX.is_eligible(&Position::RB));
+        assert!(RosterPosition::IDP_FLEX.is_eligible(&Position::LB));
+        assert!(RosterPosition::LB.is_eligible(&Position::OLB));
+        assert!(!RosterPosition::IDP_FLEX.is_eligible(&Position::K));
+        assert!(RosterPosition::Unknown("X".to_string())
+            .eligible_positions()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_injury_status_from_str() {
+        assert_eq!("Out".parse::<InjuryStatus>().unwrap(), InjuryStatus::Out);
+        assert_eq!(
+            "Doubtful".parse::<InjuryStatus>().unwrap(),
+            InjuryStatus::Doubtful
+        );
+        assert_eq!(
+            "DNR".parse::<InjuryStatus>().unwrap(),
+            InjuryStatus::DidNotReport
+        );
+        assert_eq!(
+            "Sus".parse::<InjuryStatus>().unwrap(),
+            InjuryStatus::Suspended
+        );
+        assert!("Sore".parse::<InjuryStatus>().is_err());
+    }
+
+    #[test]
+    fn test_deserialize_nfl_player_statuses() {
+        let json_str = r#"
+        {
+            "player_id": "4046",
+            "first_name": "Patrick",
+            "last_name": "Mahomes",
+            "sport": "nfl",
+            "active": true,
+            "status": "Active",
+            "injury_status": "Questionable",
+            "practice_participation": "Limited"
+        }"#;
+
+        let player: NflPlayer = serde_json::from_str(json_str).unwrap();
+        assert_eq!(player.status, Some(PlayerStatus::Active));
+        assert_eq!(player.injury_status, InjuryStatus::Questionable);
+        assert_eq!(
+            player.practice_participation,
+            Some(PracticeParticipation::Limited)
+        );
+
+        let json_str = r#"
+        {
+            "player_id": "4046",
+            "first_name": "Patrick",
+            "last_name": "Mahomes",
+            "sport": "nfl",
+            "active": true,
+            "status": "Retired",
+            "injury_status": null
+        }"#;
+
+        let player: NflPlayer = serde_json::from_str(json_str).unwrap();
+        assert_eq!(
+            player.status,
+            Some(PlayerStatus::Unknown("Retired".to_string()))
+        );
+        assert_eq!(player.injury_status, InjuryStatus::Healthy);
+        assert_eq!(player.practice_participation, None);
+
+        let value = serde_json::to_value(&player).unwrap();
+        assert_eq!(value["injury_status"], Value::Null);
+        assert_eq!(value["status"], "Retired");
+    }
+
+    #[test]
+    fn test_deserialize_sport_state_from_json() {
+        let json_str = r#"
+        {
+            "week": 2,
+            "season_type": "regular",
+            "season_start_date": "2023-09-07",
+            "season": "2023",
+            "previous_season": "2022",
+            "leg": 2,
+            "league_season": "2023",
+            "league_create_season": "2024",
+            "display_week": 2
+        }"#;
+
+        let state: SportState = serde_json::from_str(json_str).unwrap();
+        assert_eq!(state.season_type, SeasonType::Regular);
+        assert_eq!(state.season_start_date, Date::new(2023, 9, 7));
+        assert_eq!(state.season, Season(2023));
+        assert_eq!(Some(state.previous_season), state.season.previous());
+        assert_eq!(Season(0).previous(), None);
+        assert_eq!(Season(u16::MAX).next(), None);
+        assert_eq!(
+            serde_json::from_str::<SeasonType>("\"mid\"").unwrap(),
+            SeasonType::Unknown("mid".to_string())
+        );
+        assert_eq!(state.league_create_season, Season(2024));
+
+        let value = serde_json::to_value(&state).unwrap();
+        assert_eq!(value["season"], "2023");
+        assert_eq!(value["season_start_date"], "2023-09-07");
+    }
+
+    #[test]
+    fn test_date_parsing_and_timestamps() {
+        assert_eq!(
+            "2024-02-29".parse::<Date>().unwrap(),
+            Date::new(2024, 2, 29).unwrap()
+        );
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("2023-9".parse::<Date>().is_err());
+        assert!("not a date".parse::<Date>().is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_timestamp_dates() {
+        assert_eq!(Timestamp(0).date(), Date::new(1970, 1, 1));
+        assert_eq!(Timestamp(1704267066920).date(), Date::new(2024, 1, 3));
+        assert_eq!(Timestamp(951782400000).date(), Date::new(2000, 2, 29));
+        assert_eq!(Timestamp(u64::MAX).to_datetime(), None);
+    }
+
+    #[test]
+    fn test_nfl_team_abbreviations_and_aliases() {
+        assert_eq!(NflTeam::ALL.len(), 32);
+        for team in NflTeam::ALL {
+            assert_eq!(team.abbreviation().parse::<NflTeam>().unwrap(), team);
+            for alias in team.aliases() {
+                assert_eq!(alias.parse::<NflTeam>().unwrap(), team);
+            }
+        }
+
+        assert_eq!("OAK".parse::<NflTeam>().unwrap(), NflTeam::LV);
+        assert_eq!("SD".parse::<NflTeam>().unwrap(), NflTeam::LAC);
+        assert_eq!("STL".parse::<NflTeam>().unwrap(), NflTeam::LAR);
+        assert_eq!(NflTeam::KC.name(), Some("Kansas City Chiefs"));
+        assert_eq!(NflTeam::KC.division(), Some(Division::AfcWest));
+        assert_eq!(NflTeam::GB.conference(), Some(Conference::NFC));
+        for division in [Division::AfcEast, Division::NfcWest] {
+            assert_eq!(division.teams().count(), 4);
+        }
+    }
+
+    #[test]
+    fn test_defense_player_ids_resolve_to_teams() {
+        assert_eq!(NflTeam::from_defense_player_id("KC"), Some(NflTeam::KC));
+        assert_eq!(NflTeam::from_defense_player_id("4046"), None);
+        assert_eq!(NflTeam::SF.defense_player_id(), "SF");
+
+        let json_str = r#"
+        {
+            "player_id": "KC",
+            "first_name": "Kansas City",
+            "last_name": "Chiefs",
+            "sport": "nfl",
+            "active": true,
+            "team": "KC",
+            "position": "DEF"
+        }"#;
+
+        let player: NflPlayer = serde_json::from_str(json_str).unwrap();
+        assert_eq!(player.team, Some(NflTeam::KC));
+        assert_eq!(player.defense_team(), Some(NflTeam::KC));
+    }
+
+    #[test]
+    fn test_unknown_team_keeps_its_abbreviation() {
+        let json_str = r#"
+        {
+            "player_id": "9999",
+            "first_name": "Expansion",
+            "last_name": "Player",
+            "sport": "nfl",
+            "active": true,
+            "team": "LON",
+            "position": "WR"
+        }"#;
+
+        let player: NflPlayer = serde_json::from_str(json_str).unwrap();
+        let team = player.team.clone().unwrap();
+        assert_eq!(team, NflTeam::Unknown("LON".to_string()));
+        assert_eq!(team.abbreviation(), "LON");
+        assert_eq!(team.division(), None);
+        assert_eq!(serde_json::to_value(&player).unwrap()["team"], "LON");
+    }
+
+    #[test]
+    fn test_nfl_player_physical_and_biographical_fields() {
+        let json_str = r#"
+        {
+            "player_id": "4046",
+            "first_name": "Patrick",
+            "last_name": "Mahomes",
+            "sport": "nfl",
+            "active": true,
+            "position": "QB",
+            "fantasy_positions": ["QB"],
+            "height": "6'2\"",
+            "weight": "225",
+            "birth_date": "1995-09-17",
+            "metadata": { "rookie_year": "2017" }
+        }"#;
+
+        let mut player: NflPlayer = serde_json::from_str(json_str).unwrap();
+        assert_eq!(player.position, Some(Position::QB));
+        assert_eq!(player.fantasy_positions, Some(vec![Position::QB]));
+        assert_eq!(player.height_inches(), Some(74));
+        assert_eq!(player.weight_lbs(), Some(225));
+        assert_eq!(player.birth_date(), Date::new(1995, 9, 17));
+        assert_eq!(player.age_on(Date::new(2023, 9, 16).unwrap()), Some(27));
+        assert_eq!(player.age_on(Date::new(2023, 9, 17).unwrap()), Some(28));
+        assert_eq!(player.rookie_year(), Some(Season(2017)));
+
+        player.height = Some("74".to_string());
+        assert_eq!(player.height_inches(), Some(74));
+        player.height = Some("6' 0\"".to_string());
+        assert_eq!(player.height_inches(), Some(72));
+        player.height = Some("tall".to_string());
+        assert_eq!(player.height_inches(), None);
+        player.height = Some("30'0\"".to_string());
+        assert_eq!(player.height_inches(), None);
+    }
+
+    #[test]
+    fn test_draft_type_and_status() {
+        assert_eq!("snake".parse::<DraftType>().unwrap(), DraftType::Snake);
+        assert_eq!(
+            DraftStatus::from("pre_draft".to_string()),
+            DraftStatus::PreDraft
+        );
+        assert_eq!(
+            serde_json::from_str::<DraftStatus>("\"cancelled\"").unwrap(),
+            DraftStatus::Unknown("cancelled".to_string())
+        );
+        assert_eq!(DraftStatus::Complete.to_string(), "complete");
+        assert!("3rr".parse::<DraftType>().is_err());
+    }
+
+    #[test]
+    fn test_position_fantasy_group() {
+        assert_eq!("OLB".parse::<Position>().unwrap(), Position::OLB);
+        assert_eq!(Position::OLB.to_string(), "OLB");
+        assert_eq!(Position::OLB.fantasy_group(), Position::LB);
+        assert_eq!(Position::FS.fantasy_group(), Position::DB);
+        assert_eq!(Position::DE.fantasy_group(), Position::DL);
+        assert_eq!(Position::CB.fantasy_group(), Position::DB);
+        assert_eq!(Position::WR.fantasy_group(), Position::WR);
+        assert_eq!(
+            Position::from("XYZ".to_string()),
+            Position::Unknown("XYZ".to_string())
+        );
     }
 }
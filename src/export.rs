@@ -0,0 +1,513 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::data::*;
+use crate::standings::StandingsRow;
+
+/// One player on a roster. Field order is the CSV column order.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct RosterPlayerRow {
+    pub league_id: LeagueId,
+    pub roster_id: u8,
+    pub owner_id: Option<OwnerId>,
+    pub owner: Option<String>,
+    pub player_id: PlayerId,
+    /// The starting slot the player fills (`QB`, `FLEX`, ...), or `BN`, `IR` or `TAXI`.
+    pub slot: String,
+}
+
+/// What one player scored for one roster in one week. Field order is the CSV column order.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct MatchupPlayerRow {
+    pub week: u8,
+    pub matchup_id: Option<u8>,
+    pub roster_id: u8,
+    pub player_id: PlayerId,
+    pub points: f64,
+    pub started: bool,
+}
+
+/// `StandingsRow` with its records split into columns. Field order is the CSV column order.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct StandingsCsvRow {
+    pub rank: u8,
+    pub roster_id: u8,
+    pub owner_id: Option<OwnerId>,
+    pub division: Option<u8>,
+    pub division_rank: Option<u8>,
+    pub wins: u16,
+    pub losses: u16,
+    pub ties: u16,
+    pub head_to_head_wins: u16,
+    pub head_to_head_losses: u16,
+    pub head_to_head_ties: u16,
+    pub median_wins: u16,
+    pub median_losses: u16,
+    pub median_ties: u16,
+    pub division_wins: u16,
+    pub division_losses: u16,
+    pub division_ties: u16,
+    pub points_for: f64,
+    pub points_against: f64,
+    /// Such as `3W`.
+    pub streak: Option<String>,
+}
+
+impl From<&StandingsRow> for StandingsCsvRow {
+    fn from(row: &StandingsRow) -> Self {
+        StandingsCsvRow {
+            rank: row.rank,
+            roster_id: row.roster_id,
+            owner_id: row.owner_id.clone(),
+            division: row.division,
+            division_rank: row.division_rank,
+            wins: row.record.wins,
+            losses: row.record.losses,
+            ties: row.record.ties,
+            head_to_head_wins: row.head_to_head_record.wins,
+            head_to_head_losses: row.head_to_head_record.losses,
+            head_to_head_ties: row.head_to_head_record.ties,
+            median_wins: row.median_record.wins,
+            median_losses: row.median_record.losses,
+            median_ties: row.median_record.ties,
+            division_wins: row.division_record.wins,
+            division_losses: row.division_record.losses,
+            division_ties: row.division_record.ties,
+            points_for: row.points_for,
+            points_against: row.points_against,
+            streak: row.streak.map(|s| s.to_string()),
+        }
+    }
+}
+
+/// `NflPlayer` flattened to scalar columns. Field order is the CSV column order.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct PlayerRow {
+    pub player_id: PlayerId,
+    pub full_name: Option<String>,
+    pub first_name: String,
+    pub last_name: String,
+    pub position: Option<String>,
+    /// Separated by `;`.
+    pub fantasy_positions: Option<String>,
+    pub team: Option<String>,
+    pub number: Option<u8>,
+    pub status: Option<String>,
+    pub active: bool,
+    pub injury_status: Option<String>,
+    pub injury_body_part: Option<String>,
+    pub injury_notes: Option<String>,
+    pub injury_start_date: Option<String>,
+    pub practice_participation: Option<String>,
+    pub practice_description: Option<String>,
+    pub depth_chart_position: Option<String>,
+    pub depth_chart_order: Option<u8>,
+    pub search_rank: Option<u64>,
+    pub age: Option<u8>,
+    pub birth_date: Option<String>,
+    pub years_exp: Option<i8>,
+    pub height_inches: Option<u8>,
+    pub weight_lbs: Option<u16>,
+    pub college: Option<String>,
+    pub high_school: Option<String>,
+    pub birth_city: Option<String>,
+    pub birth_state: Option<String>,
+    pub birth_country: Option<String>,
+    pub news_updated: Option<u64>,
+    pub espn_id: Option<u64>,
+    pub yahoo_id: Option<i32>,
+    pub sportradar_id: Option<String>,
+    pub gsis_id: Option<String>,
+    pub rotowire_id: Option<u32>,
+    pub rotoworld_id: Option<u64>,
+    pub stats_id: Option<u32>,
+    pub swish_id: Option<u32>,
+    pub fantasy_data_id: Option<u64>,
+}
+
+impl From<&NflPlayer> for PlayerRow {
+    fn from(player: &NflPlayer) -> Self {
+        let trimmed = |s: &Option<String>| {
+            s.as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+
+        PlayerRow {
+            player_id: player.player_id.clone(),
+            full_name: player.full_name.clone(),
+            first_name: player.first_name.clone(),
+            last_name: player.last_name.clone(),
+            position: player.position.as_ref().map(|p| p.to_string()),
+            fantasy_positions: player.fantasy_positions.as_ref().map(|positions| {
+                positions
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<String>>()
+                    .join(";")
+            }),
+            team: player.team.as_ref().map(|t| t.to_string()),
+            number: player.number,
+            status: player.status.as_ref().map(|s| s.to_string()),
+            active: player.active,
+            injury_status: player.injury_status.code().map(str::to_string),
+            injury_body_part: player.injury_body_part.clone(),
+            injury_notes: player.injury_notes.clone(),
+            injury_start_date: player.injury_start_date.clone(),
+            practice_participation: player
+                .practice_participation
+                .as_ref()
+                .map(|p| p.to_string()),
+            practice_description: player.practice_description.clone(),
+            depth_chart_position: player.depth_chart_position.clone(),
+            depth_chart_order: player.depth_chart_order,
+            search_rank: player.search_rank,
+            age: player.age,
+            birth_date: player.birth_date.clone(),
+            years_exp: player.years_exp,
+            height_inches: player.height_inches(),
+            weight_lbs: player.weight_lbs(),
+            college: player.college.clone(),
+            high_school: player.high_school.clone(),
+            birth_city: player.birth_city.clone(),
+            birth_state: player.birth_state.clone(),
+            birth_country: player.birth_country.clone(),
+            news_updated: player.news_updated.map(|t| t.0),
+            espn_id: player.espn_id,
+            yahoo_id: player.yahoo_id,
+            sportradar_id: trimmed(&player.sportradar_id),
+            gsis_id: trimmed(&player.gsis_id),
+            rotowire_id: player.rotowire_id,
+            rotoworld_id: player.rotoworld_id,
+            stats_id: player.stats_id,
+            swish_id: player.swish_id,
+            fantasy_data_id: player.fantasy_data_id,
+        }
+    }
+}
+
+/// One row per rostered player, rosters in `roster_id` order. Starters come
+/// first in slot order, followed by the bench, injured reserve and taxi squad.
+pub fn roster_rows(
+    league: &League,
+    users: &[SleeperUser],
+    rosters: &[Roster],
+) -> Vec<RosterPlayerRow> {
+    let starting_slots: Vec<&RosterPosition> = league
+        .roster_positions
+        .iter()
+        .filter(|p| **p != RosterPosition::BN)
+        .collect();
+
+    let mut rosters: Vec<&Roster> = rosters.iter().collect();
+    rosters.sort_by_key(|r| r.roster_id);
+
+    let mut rows = Vec::new();
+    for roster in rosters {
+        let owner = roster
+            .owner_id
+            .as_ref()
+            .and_then(|id| users.iter().find(|u| &u.user_id == id))
+            .and_then(|u| u.display_name.clone());
+        let reserve = player_ids(&roster.reserve);
+        let taxi = player_ids(&roster.taxi);
+
+        let mut row = |player_id: &str, slot: String| {
+            rows.push(RosterPlayerRow {
+                league_id: roster.league_id.clone(),
+                roster_id: roster.roster_id,
+                owner_id: roster.owner_id.clone(),
+                owner: owner.clone(),
+                player_id: player_id.to_string(),
+                slot,
+            })
+        };
+
+        // Sleeper fills empty starting slots with "0"
+        for (player_id, slot) in roster.starters.iter().zip(&starting_slots) {
+            if player_id != "0" {
+                row(player_id, slot.to_string());
+            }
+        }
+
+        let mut bench: Vec<&String> = roster
+            .players
+            .iter()
+            .filter(|p| !roster.starters.contains(p))
+            .collect();
+        let slot = |player_id: &String| {
+            if reserve.contains(player_id) {
+                "IR"
+            } else if taxi.contains(player_id) {
+                "TAXI"
+            } else {
+                "BN"
+            }
+        };
+        bench.sort_by_key(|p| (taxi.contains(p), reserve.contains(p)));
+        for player_id in bench {
+            row(player_id, slot(player_id).to_string());
+        }
+    }
+
+    rows
+}
+
+/// One row per player per roster per week, ordered by week then `roster_id`.
+/// Starters come first in lineup order, then the bench.
+pub fn matchup_rows(matchups: &BTreeMap<u8, Vec<Matchup>>) -> Vec<MatchupPlayerRow> {
+    let mut rows = Vec::new();
+    for (&week, week_matchups) in matchups {
+        let mut week_matchups: Vec<&Matchup> = week_matchups.iter().collect();
+        week_matchups.sort_by_key(|m| m.roster_id);
+
+        for matchup in week_matchups {
+            let starters = matchup.starters.iter().filter(|p| *p != "0");
+            let bench = matchup
+                .players
+                .iter()
+                .flatten()
+                .filter(|p| !matchup.is_starter(p));
+
+            for player_id in starters.chain(bench) {
+                rows.push(MatchupPlayerRow {
+                    week,
+                    matchup_id: matchup.matchup_id,
+                    roster_id: matchup.roster_id,
+                    player_id: player_id.clone(),
+                    points: matchup.player_points(player_id).unwrap_or(0.0),
+                    started: matchup.is_starter(player_id),
+                });
+            }
+        }
+    }
+
+    rows
+}
+
+/// Every NFL player ordered by `player_id`. Other sports aren't modelled yet
+/// and produce an `ExportError`.
+pub fn player_rows(players: &AllPlayers) -> Result<Vec<PlayerRow>, SleeperError> {
+    let AllPlayers::NFL(players) = players else {
+        return Err(SleeperError::ExportError(
+            "only NFL players can be exported".to_string(),
+        ));
+    };
+
+    let mut rows: Vec<PlayerRow> = players.values().map(PlayerRow::from).collect();
+    rows.sort_by(|a, b| a.player_id.cmp(&b.player_id));
+    Ok(rows)
+}
+
+pub fn write_rosters_csv<W: Write>(
+    writer: W,
+    league: &League,
+    users: &[SleeperUser],
+    rosters: &[Roster],
+) -> Result<(), SleeperError> {
+    write_csv(writer, roster_rows(league, users, rosters))
+}
+
+pub fn write_matchups_csv<W: Write>(
+    writer: W,
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+) -> Result<(), SleeperError> {
+    write_csv(writer, matchup_rows(matchups))
+}
+
+pub fn write_standings_csv<W: Write>(
+    writer: W,
+    standings: &[StandingsRow],
+) -> Result<(), SleeperError> {
+    write_csv(writer, standings.iter().map(StandingsCsvRow::from))
+}
+
+pub fn write_players_csv<W: Write>(writer: W, players: &AllPlayers) -> Result<(), SleeperError> {
+    write_csv(writer, player_rows(players)?)
+}
+
+/// Write `rows` as CSV with a header row, which is taken from `T::default()`
+/// so that it is written even when there are no rows.
+pub(crate) fn write_csv<W: Write, T: Serialize + Default>(
+    writer: W,
+    rows: impl IntoIterator<Item = T>,
+) -> Result<(), SleeperError> {
+    let mut csv = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(writer);
+    csv.write_record(&csv_header::<T>()?)
+        .map_err(|e| SleeperError::ExportError(e.to_string()))?;
+
+    for row in rows {
+        csv.serialize(row)
+            .map_err(|e| SleeperError::ExportError(e.to_string()))?;
+    }
+
+    csv.flush()
+        .map_err(|e| SleeperError::ExportError(e.to_string()))
+}
+
+// The csv crate only writes a header along with the first row, so write a
+// throwaway row and read its header back.
+fn csv_header<T: Serialize + Default>() -> Result<csv::StringRecord, SleeperError> {
+    let mut csv = csv::Writer::from_writer(Vec::new());
+    csv.serialize(T::default())
+        .map_err(|e| SleeperError::ExportError(e.to_string()))?;
+    let written = csv
+        .into_inner()
+        .map_err(|e| SleeperError::ExportError(e.to_string()))?;
+
+    csv::Reader::from_reader(written.as_slice())
+        .headers()
+        .cloned()
+        .map_err(|e| SleeperError::ExportError(e.to_string()))
+}
+
+/// Player IDs out of one of `Roster`'s untyped lists, which may be null.
+fn player_ids(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|ids| {
+            ids.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::standings;
+    use crate::test_support::{matchup_with_players, snapshot};
+
+    fn csv_lines<F>(write: F) -> Vec<String>
+    where
+        F: FnOnce(&mut Vec<u8>) -> Result<(), SleeperError>,
+    {
+        let mut csv = Vec::new();
+        write(&mut csv).unwrap();
+        String::from_utf8(csv)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_roster_rows_slots() {
+        let mut snapshot = snapshot();
+        let roster = &mut snapshot.rosters[0];
+        roster.starters = vec!["4046".to_string(), "0".to_string(), "KC".to_string()];
+        roster.players = vec![
+            "4046".to_string(),
+            "9999".to_string(),
+            "1234".to_string(),
+            "KC".to_string(),
+            "5678".to_string(),
+        ];
+        roster.reserve = serde_json::json!(["9999"]);
+        roster.taxi = serde_json::json!(["1234"]);
+
+        let rows = roster_rows(&snapshot.league, &snapshot.users, &snapshot.rosters);
+        let slots: Vec<(&str, &str)> = rows
+            .iter()
+            .filter(|r| r.roster_id == 1)
+            .map(|r| (r.player_id.as_str(), r.slot.as_str()))
+            .collect();
+        assert_eq!(
+            slots,
+            vec![
+                ("4046", "QB"),
+                ("KC", "RB"),
+                ("5678", "BN"),
+                ("9999", "IR"),
+                ("1234", "TAXI")
+            ]
+        );
+        assert_eq!(rows[0].owner.as_deref(), Some("Alice"));
+
+        let lines = csv_lines(|w| {
+            write_rosters_csv(w, &snapshot.league, &snapshot.users, &snapshot.rosters)
+        });
+        assert_eq!(
+            lines[0],
+            "league_id,roster_id,owner_id,owner,player_id,slot"
+        );
+        assert_eq!(lines.len(), 7);
+    }
+
+    #[test]
+    fn test_matchup_rows() {
+        let matchups: BTreeMap<u8, Vec<Matchup>> = [
+            (
+                2,
+                vec![matchup_with_players(
+                    2,
+                    &[("6794", 20.5, true), ("7564", 3.0, false)],
+                )],
+            ),
+            (1, vec![matchup_with_players(1, &[("4046", 25.0, true)])]),
+        ]
+        .into_iter()
+        .collect();
+
+        let lines = csv_lines(|w| write_matchups_csv(w, &matchups));
+        assert_eq!(
+            lines,
+            vec![
+                "week,matchup_id,roster_id,player_id,points,started",
+                "1,1,1,4046,25.0,true",
+                "2,1,2,6794,20.5,true",
+                "2,1,2,7564,3.0,false",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_csv_has_header() {
+        let lines = csv_lines(|w| write_matchups_csv(w, &BTreeMap::new()));
+        assert_eq!(
+            lines,
+            vec!["week,matchup_id,roster_id,player_id,points,started"]
+        );
+
+        let lines = csv_lines(|w| write_standings_csv(w, &[]));
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("rank,roster_id,owner_id,"));
+    }
+
+    #[test]
+    fn test_standings_and_players_csv() {
+        let snapshot = snapshot();
+        let table = standings(&snapshot.league, &snapshot.rosters, &snapshot.matchups);
+        let lines = csv_lines(|w| write_standings_csv(w, &table));
+        assert!(lines[0].starts_with("rank,roster_id,owner_id,division,division_rank,wins,"));
+        assert_eq!(
+            lines[1],
+            "1,1,owner1,1,1,1,0,0,1,0,0,0,0,0,1,0,0,100.0,90.0,1W"
+        );
+
+        let players = AllPlayers::NFL(
+            snapshot
+                .players
+                .iter()
+                .map(|(id, p)| (id.clone(), p.clone()))
+                .collect(),
+        );
+        let lines = csv_lines(|w| write_players_csv(w, &players));
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("player_id,full_name,first_name,last_name,position,"));
+        assert!(lines[1].starts_with("4046,Patrick Mahomes,Patrick,Mahomes,QB,,KC,"));
+
+        assert!(matches!(
+            write_players_csv(Vec::new(), &AllPlayers::NBA(Default::default())),
+            Err(SleeperError::ExportError(_))
+        ));
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::*;
+use crate::standings::Record;
+
+/// Everything fetched for one season of a league.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeagueSeason {
+    pub league: League,
+    pub users: Vec<SleeperUser>,
+    pub rosters: Vec<Roster>,
+    pub winners_bracket: Vec<BracketMatchup>,
+    pub losers_bracket: Vec<BracketMatchup>,
+}
+
+impl LeagueSeason {
+    pub fn user(&self, user_id: &str) -> Option<&SleeperUser> {
+        self.users.iter().find(|u| u.user_id == user_id)
+    }
+
+    /// The user who owned `roster_id` this season.
+    pub fn owner_of(&self, roster_id: u8) -> Option<&SleeperUser> {
+        let roster = self.rosters.iter().find(|r| r.roster_id == roster_id)?;
+        self.user(roster.owner_id.as_deref()?)
+    }
+
+    /// The roster which won the championship game, once it has been played.
+    pub fn champion(&self) -> Option<u8> {
+        self.winners_bracket
+            .iter()
+            .find(|game| game.place == Some(1))?
+            .winner
+    }
+}
+
+/// A league across all of its seasons, oldest first, with each owner's
+/// seasons linked by `user_id`. Roster IDs and display names can change from
+/// one season to the next, user IDs do not.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeagueHistory {
+    pub seasons: Vec<LeagueSeason>,
+    /// Ordered by each owner's first season, then by `user_id`.
+    pub owners: Vec<OwnerHistory>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct OwnerHistory {
+    pub user_id: OwnerId,
+    /// Oldest first.
+    pub seasons: Vec<OwnerSeason>,
+}
+
+/// One owner's roster in one season.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct OwnerSeason {
+    pub season: Season,
+    pub league_id: LeagueId,
+    pub roster_id: u8,
+    pub display_name: Option<String>,
+    pub team_name: Option<String>,
+    pub record: Record,
+    pub points_for: f64,
+    pub champion: bool,
+}
+
+impl LeagueHistory {
+    /// Link owners across `seasons`, which must be ordered oldest first.
+    /// Rosters without an owner are left out of `owners`.
+    pub fn from_seasons(seasons: Vec<LeagueSeason>) -> Self {
+        let mut by_owner: BTreeMap<&str, Vec<OwnerSeason>> = BTreeMap::new();
+
+        for season in &seasons {
+            let champion = season.champion();
+            for roster in &season.rosters {
+                let Some(owner_id) = roster.owner_id.as_deref() else {
+                    continue;
+                };
+                let user = season.user(owner_id);
+
+                by_owner.entry(owner_id).or_default().push(OwnerSeason {
+                    season: season.league.season,
+                    league_id: season.league.league_id.clone(),
+                    roster_id: roster.roster_id,
+                    display_name: user.and_then(|u| u.display_name.clone()),
+                    team_name: user.and_then(|u| u.team_name()).map(str::to_string),
+                    record: Record {
+                        wins: u16::from(roster.settings.wins),
+                        losses: roster.settings.losses,
+                        ties: roster.settings.ties,
+                    },
+                    points_for: roster.settings.points_for(),
+                    champion: champion == Some(roster.roster_id),
+                });
+            }
+        }
+
+        let mut owners: Vec<OwnerHistory> = by_owner
+            .into_iter()
+            .map(|(user_id, seasons)| OwnerHistory {
+                user_id: user_id.to_string(),
+                seasons,
+            })
+            .collect();
+        // Stable sort, so owners who joined together stay ordered by user_id
+        owners.sort_by_key(|owner| owner.seasons[0].season);
+
+        LeagueHistory { seasons, owners }
+    }
+
+    pub fn owner(&self, user_id: &str) -> Option<&OwnerHistory> {
+        self.owners.iter().find(|o| o.user_id == user_id)
+    }
+
+    /// The most recent season.
+    pub fn current(&self) -> Option<&LeagueSeason> {
+        self.seasons.last()
+    }
+}
+
+impl OwnerHistory {
+    /// The display name from the owner's most recent season.
+    pub fn display_name(&self) -> Option<&str> {
+        self.seasons
+            .iter()
+            .rev()
+            .find_map(|s| s.display_name.as_deref())
+    }
+
+    /// Regular season record summed across every season.
+    pub fn record(&self) -> Record {
+        self.seasons
+            .iter()
+            .fold(Record::default(), |total, s| Record {
+                wins: total.wins + s.record.wins,
+                losses: total.losses + s.record.losses,
+                ties: total.ties + s.record.ties,
+            })
+    }
+
+    pub fn championships(&self) -> usize {
+        self.seasons.iter().filter(|s| s.champion).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{league, user};
+
+    fn roster(roster_id: u8, owner_id: Option<&str>, wins: u8, losses: u16) -> Roster {
+        serde_json::from_value(serde_json::json!({
+            "taxi": null,
+            "starters": [],
+            "settings": { "wins": wins, "losses": losses, "fpts": 1200, "fpts_decimal": 50 },
+            "roster_id": roster_id,
+            "reserve": null,
+            "players": [],
+            "player_map": null,
+            "owner_id": owner_id,
+            "metadata": null,
+            "league_id": "999999999999999999",
+            "keepers": null,
+            "co_owners": null
+        }))
+        .unwrap()
+    }
+
+    fn final_won_by(roster_id: u8) -> Vec<BracketMatchup> {
+        serde_json::from_str(&format!(
+            r#"[
+                {{ "r": 1, "m": 1, "t1": 1, "t2": 2, "w": 1, "l": 2 }},
+                {{ "r": 2, "m": 2, "t1": {0}, "t2": 3, "w": {0}, "l": 3, "t1_from": {{ "w": 1 }}, "p": 1 }}
+            ]"#,
+            roster_id
+        ))
+        .unwrap()
+    }
+
+    fn season(year: u16, users: Vec<SleeperUser>, rosters: Vec<Roster>) -> LeagueSeason {
+        let mut league = league(15, 0);
+        league.season = Season(year);
+        league.league_id = format!("league{}", year);
+        LeagueSeason {
+            league,
+            users,
+            rosters,
+            winners_bracket: Vec::new(),
+            losers_bracket: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_owners_followed_across_roster_and_name_changes() {
+        let mut first = season(
+            2022,
+            vec![
+                user("u1", "Alice", Some("Gridiron")),
+                user("u2", "Bob", None),
+            ],
+            vec![roster(1, Some("u1"), 9, 5), roster(2, Some("u2"), 5, 9)],
+        );
+        first.winners_bracket = final_won_by(1);
+
+        // Rosters swap IDs, u1 changes display name and a third owner joins
+        let second = season(
+            2023,
+            vec![
+                user("u1", "Alicia", Some("Gridiron II")),
+                user("u2", "Bob", None),
+                user("u3", "Carol", None),
+            ],
+            vec![
+                roster(1, Some("u2"), 10, 4),
+                roster(2, Some("u1"), 7, 7),
+                roster(3, Some("u3"), 4, 10),
+                roster(4, None, 0, 14),
+            ],
+        );
+
+        let history = LeagueHistory::from_seasons(vec![first, second]);
+        assert_eq!(history.current().unwrap().league.season, Season(2023));
+
+        let order: Vec<&str> = history.owners.iter().map(|o| o.user_id.as_str()).collect();
+        assert_eq!(order, vec!["u1", "u2", "u3"]);
+
+        let alice = history.owner("u1").unwrap();
+        let roster_ids: Vec<u8> = alice.seasons.iter().map(|s| s.roster_id).collect();
+        assert_eq!(roster_ids, vec![1, 2]);
+        assert_eq!(alice.display_name(), Some("Alicia"));
+        assert_eq!(alice.seasons[0].team_name.as_deref(), Some("Gridiron"));
+        assert_eq!(alice.record().to_string(), "16-12-0");
+        assert_eq!(alice.championships(), 1);
+        assert_eq!(alice.seasons[0].points_for, 1200.5);
+
+        let bob = history.owner("u2").unwrap();
+        assert_eq!(bob.championships(), 0);
+        assert_eq!(bob.seasons[1].team_name, None);
+        assert_eq!(history.owner("u3").unwrap().seasons.len(), 1);
+    }
+
+    #[test]
+    fn test_bracket_deserialization() {
+        let bracket = final_won_by(2);
+        assert_eq!(bracket[1].team_1_from.as_ref().unwrap().winner_of, Some(1));
+        assert_eq!(bracket[1].place, Some(1));
+        assert_eq!(bracket[0].place, None);
+        assert_eq!(bracket[0].team_1_from, None);
+    }
+}
@@ -1,2 +1,23 @@
+pub mod analytics;
 pub mod client;
+#[cfg(feature = "arrow")]
+pub mod columnar;
+pub mod crosswalk;
 pub mod data;
+pub mod export;
+pub mod history;
+pub mod lineups;
+pub mod notify;
+#[cfg(feature = "playoffs")]
+pub mod playoffs;
+pub mod scoring;
+pub mod search;
+pub mod snapshot;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod standings;
+#[cfg(test)]
+mod test_support;
+pub mod trades;
+pub mod waivers;
+pub mod watch;
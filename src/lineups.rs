@@ -0,0 +1,434 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::*;
+
+/// Cost of leaving a starting slot empty. Large enough that the solver only
+/// does so when no eligible player is left, whatever the points involved.
+const EMPTY_SLOT_COST: f64 = 1e6;
+/// Cost of putting a player in a slot they may not play.
+const INELIGIBLE_COST: f64 = 1e9;
+
+/// A player who could be put in the lineup.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LineupCandidate {
+    pub player_id: PlayerId,
+    /// Every position the player is eligible at, as fantasy groups (`DE` is `DL`).
+    pub positions: Vec<Position>,
+    pub points: f64,
+}
+
+impl LineupCandidate {
+    /// Eligibility comes from `fantasy_positions`, falling back to `position`.
+    pub fn new(player: &NflPlayer, points: f64) -> Self {
+        let positions = match &player.fantasy_positions {
+            Some(positions) if !positions.is_empty() => positions.clone(),
+            _ => player.position.iter().cloned().collect(),
+        };
+
+        LineupCandidate {
+            player_id: player.player_id.clone(),
+            positions: positions.iter().map(Position::fantasy_group).collect(),
+            points,
+        }
+    }
+
+    pub fn fits(&self, slot: &RosterPosition) -> bool {
+        self.positions.iter().any(|p| slot.is_eligible(p))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LineupSlot {
+    pub slot: RosterPosition,
+    /// `None` when no eligible player was left for the slot.
+    pub player_id: Option<PlayerId>,
+    pub points: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct OptimalLineup {
+    /// In the order of the slots given to `optimal_lineup`.
+    pub slots: Vec<LineupSlot>,
+    pub points: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PlayerPoints {
+    pub player_id: PlayerId,
+    pub points: f64,
+}
+
+/// How close one roster's lineup came to the best possible one in one week.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LineupEfficiency {
+    pub week: u8,
+    pub roster_id: u8,
+    pub optimal_points: f64,
+    pub actual_points: f64,
+    /// `actual_points` as a percentage of `optimal_points`.
+    pub efficiency: f64,
+    pub optimal_lineup: Vec<LineupSlot>,
+    /// Benched players who were part of the optimal lineup, most points first.
+    pub should_have_started: Vec<PlayerPoints>,
+    /// Starters who were not part of the optimal lineup, most points first.
+    pub should_have_benched: Vec<PlayerPoints>,
+}
+
+/// The highest scoring way to fill `slots` from `candidates`. Bench slots are
+/// ignored. Each candidate fills at most one slot and only slots they are
+/// eligible for, so flex slots are filled with whoever is worth most there
+/// rather than whoever is left.
+pub fn optimal_lineup(slots: &[RosterPosition], candidates: &[LineupCandidate]) -> OptimalLineup {
+    let slots: Vec<&RosterPosition> = slots.iter().filter(|s| s.is_starter()).collect();
+    if slots.is_empty() {
+        return OptimalLineup {
+            slots: Vec::new(),
+            points: 0.0,
+        };
+    }
+
+    // One column per candidate, then one "nobody" column per slot
+    let cost: Vec<Vec<f64>> = slots
+        .iter()
+        .map(|slot| {
+            candidates
+                .iter()
+                .map(|c| {
+                    if c.fits(slot) {
+                        -c.points
+                    } else {
+                        INELIGIBLE_COST
+                    }
+                })
+                .chain(std::iter::repeat_n(EMPTY_SLOT_COST, slots.len()))
+                .collect()
+        })
+        .collect();
+
+    let lineup: Vec<LineupSlot> = assign(&cost)
+        .into_iter()
+        .zip(slots)
+        .map(|(column, slot)| match candidates.get(column) {
+            Some(candidate) if candidate.fits(slot) => LineupSlot {
+                slot: slot.clone(),
+                player_id: Some(candidate.player_id.clone()),
+                points: candidate.points,
+            },
+            _ => LineupSlot {
+                slot: slot.clone(),
+                player_id: None,
+                points: 0.0,
+            },
+        })
+        .collect();
+
+    OptimalLineup {
+        points: round(lineup.iter().map(|s| s.points).sum()),
+        slots: lineup,
+    }
+}
+
+/// Optimal against actual lineup for a single matchup. Players missing from
+/// `players` cannot be placed in the optimal lineup.
+pub fn matchup_efficiency(
+    league: &League,
+    week: u8,
+    matchup: &Matchup,
+    players: &HashMap<PlayerId, NflPlayer>,
+) -> LineupEfficiency {
+    let points = |player_id: &str| matchup.player_points(player_id).unwrap_or(0.0);
+
+    let candidates: Vec<LineupCandidate> = matchup
+        .players
+        .iter()
+        .flatten()
+        .filter_map(|id| players.get(id))
+        .map(|player| LineupCandidate::new(player, points(&player.player_id)))
+        .collect();
+    let optimal = optimal_lineup(&league.roster_positions, &candidates);
+
+    // Empty starting slots show up as "0"
+    let starters: Vec<&PlayerId> = matchup.starters.iter().filter(|id| *id != "0").collect();
+    let actual_points = round(starters.iter().map(|id| points(id)).sum());
+
+    let in_optimal = |id: &PlayerId| {
+        optimal
+            .slots
+            .iter()
+            .any(|s| s.player_id.as_ref() == Some(id))
+    };
+    let by_points = |ids: Vec<&PlayerId>| {
+        let mut players: Vec<PlayerPoints> = ids
+            .into_iter()
+            .map(|id| PlayerPoints {
+                player_id: id.clone(),
+                points: points(id),
+            })
+            .collect();
+        players.sort_by(|a, b| b.points.total_cmp(&a.points));
+        players
+    };
+
+    let should_have_started = by_points(
+        optimal
+            .slots
+            .iter()
+            .filter_map(|s| s.player_id.as_ref())
+            .filter(|id| !starters.contains(id))
+            .collect(),
+    );
+    let should_have_benched = by_points(
+        starters
+            .iter()
+            .copied()
+            .filter(|id| !in_optimal(id))
+            .collect(),
+    );
+
+    LineupEfficiency {
+        week,
+        roster_id: matchup.roster_id,
+        optimal_points: optimal.points,
+        actual_points,
+        efficiency: if optimal.points > 0.0 {
+            round(actual_points / optimal.points * 100.0)
+        } else {
+            100.0
+        },
+        optimal_lineup: optimal.slots,
+        should_have_started,
+        should_have_benched,
+    }
+}
+
+/// `matchup_efficiency` for every roster in every week, ordered by week then roster.
+pub fn lineup_efficiency(
+    league: &League,
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+    players: &HashMap<PlayerId, NflPlayer>,
+) -> Vec<LineupEfficiency> {
+    let mut rows = Vec::new();
+    for (&week, week_matchups) in matchups {
+        let mut week_matchups: Vec<&Matchup> = week_matchups.iter().collect();
+        week_matchups.sort_by_key(|m| m.roster_id);
+        for matchup in week_matchups {
+            rows.push(matchup_efficiency(league, week, matchup, players));
+        }
+    }
+
+    rows
+}
+
+/// Minimum cost assignment of every row to a distinct column (the Hungarian
+/// algorithm). Requires at least as many columns as rows; returns each row's column.
+fn assign(cost: &[Vec<f64>]) -> Vec<usize> {
+    let rows = cost.len();
+    let columns = cost[0].len();
+
+    // 1-based, with row/column 0 as the algorithm's sentinel
+    let mut u = vec![0.0; rows + 1];
+    let mut v = vec![0.0; columns + 1];
+    let mut owner = vec![0usize; columns + 1];
+    let mut way = vec![0usize; columns + 1];
+
+    for row in 1..=rows {
+        owner[0] = row;
+        let mut column = 0;
+        let mut min_slack = vec![f64::INFINITY; columns + 1];
+        let mut used = vec![false; columns + 1];
+
+        loop {
+            used[column] = true;
+            let current_row = owner[column];
+            let mut delta = f64::INFINITY;
+            let mut next_column = 0;
+
+            for j in 1..=columns {
+                if used[j] {
+                    continue;
+                }
+                let slack = cost[current_row - 1][j - 1] - u[current_row] - v[j];
+                if slack < min_slack[j] {
+                    min_slack[j] = slack;
+                    way[j] = column;
+                }
+                if min_slack[j] < delta {
+                    delta = min_slack[j];
+                    next_column = j;
+                }
+            }
+
+            for j in 0..=columns {
+                if used[j] {
+                    u[owner[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_slack[j] -= delta;
+                }
+            }
+
+            column = next_column;
+            if owner[column] == 0 {
+                break;
+            }
+        }
+
+        while column != 0 {
+            let previous = way[column];
+            owner[column] = owner[previous];
+            column = previous;
+        }
+    }
+
+    let mut assignment = vec![0; rows];
+    for j in 1..=columns {
+        if owner[j] != 0 {
+            assignment[owner[j] - 1] = j - 1;
+        }
+    }
+
+    assignment
+}
+
+fn round(points: f64) -> f64 {
+    (points * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{league, matchup_with_players};
+
+    fn slots() -> Vec<RosterPosition> {
+        [
+            "QB",
+            "RB",
+            "WR",
+            "FLEX",
+            "SUPER_FLEX",
+            "DL",
+            "IDP_FLEX",
+            "BN",
+            "BN",
+        ]
+        .into_iter()
+        .map(|s| s.parse().unwrap())
+        .collect()
+    }
+
+    fn players() -> HashMap<PlayerId, NflPlayer> {
+        [
+            ("qb1", "QB"),
+            ("qb2", "QB"),
+            ("rb1", "RB"),
+            ("rb2", "RB"),
+            ("wr1", "WR"),
+            ("wr2", "WR"),
+            ("te1", "TE"),
+            ("de1", "DE"),
+            ("lb1", "LB"),
+            ("cb1", "CB"),
+        ]
+        .into_iter()
+        .map(|(id, position)| {
+            let player: NflPlayer = serde_json::from_value(serde_json::json!({
+                "player_id": id,
+                "first_name": id,
+                "last_name": id,
+                "sport": "nfl",
+                "active": true,
+                "position": position,
+                "fantasy_positions": [position]
+            }))
+            .unwrap();
+            (id.to_string(), player)
+        })
+        .collect()
+    }
+
+    fn points() -> [(&'static str, f64); 10] {
+        [
+            ("qb1", 20.0),
+            ("qb2", 18.0),
+            ("rb1", 15.0),
+            ("rb2", 12.0),
+            ("wr1", 10.0),
+            ("wr2", 14.0),
+            ("te1", 9.0),
+            ("de1", 7.0),
+            ("lb1", 6.0),
+            ("cb1", 8.0),
+        ]
+    }
+
+    #[test]
+    fn test_optimal_lineup_respects_flex_eligibility() {
+        let players = players();
+        let candidates: Vec<LineupCandidate> = points()
+            .iter()
+            .map(|(id, points)| LineupCandidate::new(&players[*id], *points))
+            .collect();
+
+        let lineup = optimal_lineup(&slots(), &candidates);
+        assert_eq!(lineup.points, 94.0);
+
+        let filled: Vec<(String, &str)> = lineup
+            .slots
+            .iter()
+            .map(|s| (s.slot.to_string(), s.player_id.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            filled,
+            vec![
+                ("QB".to_string(), "qb1"),
+                ("RB".to_string(), "rb1"),
+                ("WR".to_string(), "wr2"),
+                ("FLEX".to_string(), "rb2"),
+                ("SUPER_FLEX".to_string(), "qb2"),
+                ("DL".to_string(), "de1"),
+                ("IDP_FLEX".to_string(), "cb1"),
+            ]
+        );
+
+        // Without a kicker on the roster the K slot stays empty
+        let mut with_kicker = slots();
+        with_kicker.push(RosterPosition::K);
+        let lineup = optimal_lineup(&with_kicker, &candidates);
+        assert_eq!(lineup.points, 94.0);
+        assert_eq!(lineup.slots.last().unwrap().player_id, None);
+    }
+
+    #[test]
+    fn test_lineup_efficiency_and_mistakes() {
+        let mut league = league(15, 0);
+        league.roster_positions = slots();
+
+        let started = ["qb1", "rb1", "wr1", "rb2", "te1", "de1", "lb1"];
+        let entries: Vec<(&str, f64, bool)> = points()
+            .iter()
+            .map(|(id, points)| (*id, *points, started.contains(id)))
+            .collect();
+        let matchups: BTreeMap<u8, Vec<Matchup>> = [(5, vec![matchup_with_players(1, &entries)])]
+            .into_iter()
+            .collect();
+
+        let rows = lineup_efficiency(&league, &matchups, &players());
+        let row = &rows[0];
+
+        assert_eq!(row.week, 5);
+        assert_eq!(row.actual_points, 79.0);
+        assert_eq!(row.optimal_points, 94.0);
+        assert_eq!(row.efficiency, 84.04);
+
+        let ids = |players: &[PlayerPoints]| {
+            players
+                .iter()
+                .map(|p| p.player_id.clone())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(ids(&row.should_have_started), vec!["qb2", "wr2", "cb1"]);
+        assert_eq!(ids(&row.should_have_benched), vec!["wr1", "te1", "lb1"]);
+    }
+}
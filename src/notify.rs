@@ -0,0 +1,495 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::data::*;
+use crate::snapshot::LeagueSnapshot;
+use crate::watch::LeagueEvent;
+
+const TRADE_COLOR: u32 = 0x5865F2;
+const WAIVER_COLOR: u32 = 0x57F287;
+const FINAL_COLOR: u32 = 0xFEE75C;
+const INJURY_COLOR: u32 = 0xED4245;
+
+/// Shown for a field with nothing in it, which Discord would otherwise reject.
+const EMPTY_FIELD: &str = "—";
+
+/// Something worth telling a league's group chat about.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum Notification {
+    Trade(Box<Transaction>),
+    WaiverClaim(Box<Transaction>),
+    MatchupFinal {
+        week: u8,
+        /// `(roster_id, points)` for both sides.
+        teams: [(u8, f64); 2],
+    },
+    InjuryToStarter {
+        roster_id: u8,
+        player_id: PlayerId,
+        status: InjuryStatus,
+    },
+}
+
+/// A rendered notification, ready to be turned into any chat service's payload.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Message {
+    pub title: String,
+    pub description: String,
+    pub fields: Vec<MessageField>,
+    /// RGB, used where the service supports it.
+    pub color: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct MessageField {
+    pub name: String,
+    pub value: String,
+}
+
+impl Notification {
+    /// The notification for a watcher event, if it is one worth sending:
+    /// completed trades and waiver claims, and starters picking up an injury.
+    pub fn from_event(event: &LeagueEvent) -> Option<Notification> {
+        match event {
+            LeagueEvent::TransactionCreated { transaction, .. } if transaction.is_complete() => {
+                match transaction.transaction_type {
+                    TransactionType::Trade => Some(Notification::Trade(transaction.clone())),
+                    TransactionType::Waiver => Some(Notification::WaiverClaim(transaction.clone())),
+                    _ => None,
+                }
+            }
+            LeagueEvent::InjuryStatusChanged {
+                roster_id,
+                player_id,
+                starter: true,
+                status,
+                ..
+            } if !status.is_healthy() => Some(Notification::InjuryToStarter {
+                roster_id: *roster_id,
+                player_id: player_id.clone(),
+                status: status.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// One `MatchupFinal` per pair of rosters sharing a `matchup_id`.
+    pub fn matchup_finals(week: u8, matchups: &[Matchup]) -> Vec<Notification> {
+        let mut finals = Vec::new();
+        for (i, a) in matchups.iter().enumerate() {
+            let Some(id) = a.matchup_id else {
+                continue;
+            };
+            if let Some(b) = matchups[i + 1..].iter().find(|b| b.matchup_id == Some(id)) {
+                finals.push(Notification::MatchupFinal {
+                    week,
+                    teams: [
+                        (a.roster_id, a.total_points()),
+                        (b.roster_id, b.total_points()),
+                    ],
+                });
+            }
+        }
+
+        finals
+    }
+
+    /// Render with team and player names taken from `snapshot`. Anything it
+    /// does not know is shown by ID.
+    pub fn render(&self, snapshot: &LeagueSnapshot) -> Message {
+        let team = |roster_id: u8| {
+            snapshot
+                .team_name(roster_id)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("Roster {}", roster_id))
+        };
+        let player = |player_id: &str| {
+            snapshot
+                .player(player_id)
+                .and_then(|p| p.full_name.clone())
+                .unwrap_or_else(|| player_id.to_string())
+        };
+
+        match self {
+            Notification::Trade(trade) => {
+                let mut roster_ids = trade.roster_ids.clone();
+                roster_ids.sort();
+
+                let fields = roster_ids
+                    .into_iter()
+                    .map(|roster_id| {
+                        let mut received: Vec<String> = trade
+                            .added_to(roster_id)
+                            .into_iter()
+                            .map(|p| player(p))
+                            .collect();
+                        received.extend(
+                            trade
+                                .draft_picks
+                                .iter()
+                                .filter(|pick| pick.owner_id == roster_id)
+                                .map(|pick| {
+                                    format!(
+                                        "{} round {} pick ({})",
+                                        pick.season,
+                                        pick.round,
+                                        team(pick.roster_id)
+                                    )
+                                }),
+                        );
+                        received.extend(
+                            trade
+                                .waiver_budget
+                                .iter()
+                                .filter(|transfer| transfer.receiver == roster_id)
+                                .map(|transfer| format!("${} FAAB", transfer.amount)),
+                        );
+
+                        MessageField {
+                            name: format!("{} receives", team(roster_id)),
+                            value: if received.is_empty() {
+                                EMPTY_FIELD.to_string()
+                            } else {
+                                received.join("\n")
+                            },
+                        }
+                    })
+                    .collect();
+
+                Message {
+                    title: "Trade completed".to_string(),
+                    description: String::new(),
+                    fields,
+                    color: TRADE_COLOR,
+                }
+            }
+            Notification::WaiverClaim(claim) => {
+                let roster_id = claim.roster_ids.first().copied().unwrap_or_default();
+                let added: Vec<String> = claim
+                    .added_to(roster_id)
+                    .into_iter()
+                    .map(|p| player(p))
+                    .collect();
+                let dropped: Vec<String> = claim
+                    .dropped_by(roster_id)
+                    .into_iter()
+                    .map(|p| player(p))
+                    .collect();
+
+                let mut description = format!("{} claimed {}", team(roster_id), added.join(", "));
+                if let Some(bid) = claim.settings.as_ref().and_then(|s| s.waiver_bid) {
+                    description.push_str(&format!(" for ${}", bid));
+                }
+                if !dropped.is_empty() {
+                    description.push_str(&format!(", dropping {}", dropped.join(", ")));
+                }
+
+                Message {
+                    title: "Waiver claim".to_string(),
+                    description,
+                    fields: Vec::new(),
+                    color: WAIVER_COLOR,
+                }
+            }
+            Notification::MatchupFinal { week, teams } => {
+                let [(a, a_points), (b, b_points)] = *teams;
+                Message {
+                    title: format!("Week {} final", week),
+                    description: format!(
+                        "{} {:.2} - {:.2} {}",
+                        team(a),
+                        a_points,
+                        b_points,
+                        team(b)
+                    ),
+                    fields: Vec::new(),
+                    color: FINAL_COLOR,
+                }
+            }
+            Notification::InjuryToStarter {
+                roster_id,
+                player_id,
+                status,
+            } => Message {
+                title: "Injury to a starter".to_string(),
+                description: format!(
+                    "{} ({}) is now {}",
+                    player(player_id),
+                    team(*roster_id),
+                    status
+                ),
+                fields: Vec::new(),
+                color: INJURY_COLOR,
+            },
+        }
+    }
+}
+
+/// The future returned by `Notifier::notify`.
+pub type NotifyFuture<'a> = Pin<Box<dyn Future<Output = Result<(), SleeperError>> + Send + 'a>>;
+
+/// Somewhere messages can be sent. Boxing the future keeps the trait object
+/// safe, so notifiers for different services fit in one `Vec<Box<dyn Notifier>>`.
+pub trait Notifier {
+    fn notify<'a>(&'a self, message: &'a Message) -> NotifyFuture<'a>;
+}
+
+/// Posts messages to a Discord channel webhook as embeds.
+pub struct DiscordWebhook {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl DiscordWebhook {
+    pub fn new(url: &str) -> Self {
+        DiscordWebhook {
+            client: webhook_client(),
+            url: url.to_string(),
+        }
+    }
+
+    pub fn payload(message: &Message) -> Value {
+        let mut embed = json!({
+            "title": message.title,
+            "color": message.color,
+            "fields": message.fields.iter().map(|field| json!({
+                "name": field.name,
+                "value": field.value,
+                "inline": true,
+            })).collect::<Vec<Value>>(),
+        });
+        if !message.description.is_empty() {
+            embed["description"] = message.description.clone().into();
+        }
+
+        json!({ "embeds": [embed] })
+    }
+}
+
+impl Notifier for DiscordWebhook {
+    fn notify<'a>(&'a self, message: &'a Message) -> NotifyFuture<'a> {
+        Box::pin(async move { post_json(&self.client, &self.url, &Self::payload(message)).await })
+    }
+}
+
+/// Posts messages to a Slack incoming webhook as Block Kit blocks.
+pub struct SlackWebhook {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl SlackWebhook {
+    pub fn new(url: &str) -> Self {
+        SlackWebhook {
+            client: webhook_client(),
+            url: url.to_string(),
+        }
+    }
+
+    pub fn payload(message: &Message) -> Value {
+        let mut blocks = vec![json!({
+            "type": "header",
+            "text": { "type": "plain_text", "text": message.title },
+        })];
+        if !message.description.is_empty() {
+            blocks.push(json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": message.description },
+            }));
+        }
+        // Slack allows at most 10 fields per section
+        for fields in message.fields.chunks(10) {
+            blocks.push(json!({
+                "type": "section",
+                "fields": fields.iter().map(|field| json!({
+                    "type": "mrkdwn",
+                    "text": format!("*{}*\n{}", field.name, field.value),
+                })).collect::<Vec<Value>>(),
+            }));
+        }
+
+        // `text` is what notifications and older clients show
+        json!({ "text": message.title, "blocks": blocks })
+    }
+}
+
+impl Notifier for SlackWebhook {
+    fn notify<'a>(&'a self, message: &'a Message) -> NotifyFuture<'a> {
+        Box::pin(async move { post_json(&self.client, &self.url, &Self::payload(message)).await })
+    }
+}
+
+fn webhook_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap()
+}
+
+async fn post_json(client: &reqwest::Client, url: &str, body: &Value) -> Result<(), SleeperError> {
+    let res = match client.post(url).json(body).send().await {
+        Ok(res) => res,
+        Err(e) => return Err(SleeperError::NetworkError(e.status())),
+    };
+
+    if !res.status().is_success() {
+        return Err(SleeperError::NetworkError(Some(res.status())));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{matchup, snapshot};
+
+    fn trade() -> Transaction {
+        serde_json::from_str::<Vec<Transaction>>(include_str!(
+            "../tests/fixtures/transactions_nfl_week_3.json"
+        ))
+        .unwrap()
+        .remove(0)
+    }
+
+    #[test]
+    fn test_render_trade() {
+        let event = LeagueEvent::TransactionCreated {
+            league_id: "1".to_string(),
+            transaction: Box::new(trade()),
+        };
+        let message = Notification::from_event(&event)
+            .unwrap()
+            .render(&snapshot());
+
+        assert_eq!(message.title, "Trade completed");
+        assert_eq!(message.fields[0].name, "Gridiron receives");
+        assert_eq!(
+            message.fields[0].value,
+            "Patrick Mahomes\n2024 round 3 pick (Bob)\n$10 FAAB"
+        );
+        assert_eq!(message.fields[1].name, "Bob receives");
+        assert_eq!(
+            message.fields[1].value,
+            "Justin Jefferson\n7564\n2024 round 1 pick (Gridiron)"
+        );
+    }
+
+    #[test]
+    fn test_render_trade_with_an_empty_side() {
+        let mut trade = trade();
+        if let Some(adds) = trade.adds.as_mut() {
+            adds.retain(|_, roster_id| *roster_id != 2);
+        }
+        trade.draft_picks.retain(|pick| pick.owner_id != 2);
+
+        let message = Notification::Trade(Box::new(trade)).render(&snapshot());
+        assert_eq!(message.fields[1].name, "Bob receives");
+        assert_eq!(message.fields[1].value, EMPTY_FIELD);
+        assert_eq!(
+            DiscordWebhook::payload(&message)["embeds"][0]["fields"][1]["value"],
+            "—"
+        );
+    }
+
+    #[test]
+    fn test_only_injuries_to_starters_notify() {
+        let injury = |starter: bool, status: InjuryStatus| LeagueEvent::InjuryStatusChanged {
+            league_id: "1".to_string(),
+            roster_id: 1,
+            player_id: "4046".to_string(),
+            starter,
+            previous: InjuryStatus::Healthy,
+            status,
+        };
+
+        assert!(Notification::from_event(&injury(false, InjuryStatus::Out)).is_none());
+        assert!(Notification::from_event(&injury(true, InjuryStatus::Healthy)).is_none());
+
+        let message = Notification::from_event(&injury(true, InjuryStatus::Doubtful))
+            .unwrap()
+            .render(&snapshot());
+        assert_eq!(
+            message.description,
+            "Patrick Mahomes (Gridiron) is now Doubtful"
+        );
+    }
+
+    #[test]
+    fn test_matchup_final_payloads() {
+        let finals = Notification::matchup_finals(
+            1,
+            &[
+                matchup(1, 1, 100.0),
+                matchup(3, 2, 80.0),
+                matchup(2, 1, 90.5),
+            ],
+        );
+        assert_eq!(finals.len(), 1);
+        let message = finals[0].render(&snapshot());
+        assert_eq!(message.description, "Gridiron 100.00 - 90.50 Bob");
+
+        let discord = DiscordWebhook::payload(&message);
+        assert_eq!(discord["embeds"][0]["title"], "Week 1 final");
+        assert_eq!(discord["embeds"][0]["color"], FINAL_COLOR);
+
+        let slack = SlackWebhook::payload(&message);
+        assert_eq!(slack["blocks"][0]["type"], "header");
+        assert_eq!(
+            slack["blocks"][1]["text"]["text"],
+            "Gridiron 100.00 - 90.50 Bob"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhooks_post_payloads() {
+        let mut server = mockito::Server::new_async().await;
+        let message = Message {
+            title: "Waiver claim".to_string(),
+            description: "Gridiron claimed Patrick Mahomes for $25".to_string(),
+            fields: Vec::new(),
+            color: WAIVER_COLOR,
+        };
+
+        let discord = server
+            .mock("POST", "/discord")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "embeds": [{ "description": "Gridiron claimed Patrick Mahomes for $25" }]
+            })))
+            .with_status(204)
+            .create_async()
+            .await;
+        let slack = server
+            .mock("POST", "/slack")
+            .match_body(mockito::Matcher::PartialJson(
+                json!({ "text": "Waiver claim" }),
+            ))
+            .with_body("ok")
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/gone")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let notifiers: Vec<Box<dyn Notifier>> = vec![
+            Box::new(DiscordWebhook::new(&format!("{}/discord", server.url()))),
+            Box::new(SlackWebhook::new(&format!("{}/slack", server.url()))),
+        ];
+        for notifier in &notifiers {
+            notifier.notify(&message).await.unwrap();
+        }
+        discord.assert_async().await;
+        slack.assert_async().await;
+
+        let result = SlackWebhook::new(&format!("{}/gone", server.url()))
+            .notify(&message)
+            .await;
+        assert!(matches!(result, Err(SleeperError::NetworkError(Some(_)))));
+    }
+}
@@ -0,0 +1,575 @@
+use std::collections::{BTreeMap, HashMap};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::data::*;
+use crate::standings::{is_regular_season_week, regular_season_games, Outcome, StandingsRow};
+
+/// How a roster's weekly score is modelled: normally distributed, never negative.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ScoreDistribution {
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+impl ScoreDistribution {
+    /// Fit a distribution to past weekly scores. Returns `None` without any scores.
+    pub fn from_scores(scores: impl IntoIterator<Item = f64>) -> Option<ScoreDistribution> {
+        let scores: Vec<f64> = scores.into_iter().collect();
+        if scores.is_empty() {
+            return None;
+        }
+
+        let n = scores.len() as f64;
+        let mean = scores.iter().sum::<f64>() / n;
+        let variance = scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+
+        Some(ScoreDistribution {
+            mean,
+            std_dev: variance.sqrt(),
+        })
+    }
+
+    fn sample(&self, rng: &mut impl Rng) -> f64 {
+        // Box-Muller; 1 - u keeps the logarithm's argument in (0, 1]
+        let u1: f64 = 1.0 - rng.random::<f64>();
+        let u2: f64 = rng.random();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+
+        (self.mean + z * self.std_dev).max(0.0)
+    }
+}
+
+/// Fit a `ScoreDistribution` per roster from its regular season scores so far.
+pub fn score_distributions(
+    league: &League,
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+) -> HashMap<u8, ScoreDistribution> {
+    let mut scores: HashMap<u8, Vec<f64>> = HashMap::new();
+    for game in regular_season_games(league, matchups) {
+        if game.opponent.is_some() {
+            scores
+                .entry(game.roster_id)
+                .or_default()
+                .push(game.points_for);
+        }
+    }
+
+    scores
+        .into_iter()
+        .filter_map(|(roster_id, scores)| {
+            ScoreDistribution::from_scores(scores).map(|d| (roster_id, d))
+        })
+        .collect()
+}
+
+/// A regular season game which has not been played yet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ScheduledGame {
+    pub week: u8,
+    pub roster_id: u8,
+    pub opponent: u8,
+}
+
+/// The regular season games still to be played. Sleeper publishes matchups for
+/// future weeks with zero points, which is what marks a week as unplayed here.
+pub fn remaining_schedule(
+    league: &League,
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+) -> Vec<ScheduledGame> {
+    let mut schedule = Vec::new();
+
+    for (&week, week_matchups) in matchups {
+        if !is_regular_season_week(league, week)
+            || week_matchups.iter().any(|m| m.total_points() != 0.0)
+        {
+            continue;
+        }
+
+        let mut by_matchup: BTreeMap<u8, Vec<u8>> = BTreeMap::new();
+        for matchup in week_matchups {
+            if let Some(id) = matchup.matchup_id {
+                by_matchup.entry(id).or_default().push(matchup.roster_id);
+            }
+        }
+
+        for pair in by_matchup.values() {
+            if let [roster_id, opponent] = pair[..] {
+                schedule.push(ScheduledGame {
+                    week,
+                    roster_id,
+                    opponent,
+                });
+            }
+        }
+    }
+
+    schedule
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SimulationConfig {
+    pub iterations: u32,
+    /// Runs with the same seed and inputs produce the same odds.
+    pub seed: u64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        SimulationConfig {
+            iterations: 10_000,
+            seed: 0,
+        }
+    }
+}
+
+/// The share of simulated seasons in which a roster reached each milestone.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PlayoffOdds {
+    pub roster_id: u8,
+    pub make_playoffs: f64,
+    pub bye: f64,
+    pub win_title: f64,
+    /// Mean regular season wins at the end of the simulated seasons.
+    pub projected_wins: f64,
+}
+
+/// Simulates the rest of a season many times over to estimate playoff odds.
+///
+/// Seeding follows the standings order (win percentage, then points for),
+/// except that division winners take the top seeds in leagues with divisions.
+/// When `playoff_teams` is not a power of two the top seeds get first round
+/// byes, e.g. two of six.
+/// `playoff_seed_type` 1 re-seeds every round so the best remaining seed faces
+/// the worst, and `playoff_round_type` decides which rounds last two weeks.
+pub struct PlayoffSimulator<'a> {
+    league: &'a League,
+    standings: &'a [StandingsRow],
+    schedule: &'a [ScheduledGame],
+    distributions: &'a HashMap<u8, ScoreDistribution>,
+}
+
+#[derive(Clone, Debug)]
+struct SimulatedRoster {
+    roster_id: u8,
+    division: Option<u8>,
+    wins: f64,
+    games: f64,
+    points_for: f64,
+}
+
+impl SimulatedRoster {
+    fn record(&mut self, outcome: Outcome) {
+        self.games += 1.0;
+        self.wins += match outcome {
+            Outcome::Win => 1.0,
+            Outcome::Tie => 0.5,
+            Outcome::Loss => 0.0,
+        };
+    }
+
+    fn win_pct(&self) -> f64 {
+        if self.games == 0.0 {
+            0.0
+        } else {
+            self.wins / self.games
+        }
+    }
+}
+
+impl<'a> PlayoffSimulator<'a> {
+    pub fn new(
+        league: &'a League,
+        standings: &'a [StandingsRow],
+        schedule: &'a [ScheduledGame],
+        distributions: &'a HashMap<u8, ScoreDistribution>,
+    ) -> Self {
+        PlayoffSimulator {
+            league,
+            standings,
+            schedule,
+            distributions,
+        }
+    }
+
+    /// Odds for every roster in `standings`, in the same order.
+    pub fn run(&self, config: SimulationConfig) -> Vec<PlayoffOdds> {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        // Rosters without a distribution play like the league's average roster
+        let n = self.distributions.len().max(1) as f64;
+        let fallback = if self.distributions.is_empty() {
+            ScoreDistribution {
+                mean: 100.0,
+                std_dev: 20.0,
+            }
+        } else {
+            ScoreDistribution {
+                mean: self.distributions.values().map(|d| d.mean).sum::<f64>() / n,
+                std_dev: self.distributions.values().map(|d| d.std_dev).sum::<f64>() / n,
+            }
+        };
+
+        let mut odds: BTreeMap<u8, PlayoffOdds> = self
+            .standings
+            .iter()
+            .map(|row| {
+                let odds = PlayoffOdds {
+                    roster_id: row.roster_id,
+                    make_playoffs: 0.0,
+                    bye: 0.0,
+                    win_title: 0.0,
+                    projected_wins: 0.0,
+                };
+                (row.roster_id, odds)
+            })
+            .collect();
+
+        let iterations = config.iterations.max(1);
+        for _ in 0..iterations {
+            let mut sample = |roster_id: u8, rng: &mut StdRng| {
+                self.distributions
+                    .get(&roster_id)
+                    .unwrap_or(&fallback)
+                    .sample(rng)
+            };
+
+            let season = self.simulate_regular_season(&mut rng, &mut sample);
+            let seeds = self.seed(&season);
+            let byes = bye_count(seeds.len());
+
+            for (i, roster_id) in seeds.iter().enumerate() {
+                let entry = odds.get_mut(roster_id).expect("seeded rosters have odds");
+                entry.make_playoffs += 1.0;
+                if i < byes {
+                    entry.bye += 1.0;
+                }
+            }
+            for roster in &season {
+                if let Some(entry) = odds.get_mut(&roster.roster_id) {
+                    entry.projected_wins += roster.wins;
+                }
+            }
+
+            if let Some(champion) = self.simulate_playoffs(&seeds, &mut rng, &mut sample) {
+                if let Some(entry) = odds.get_mut(&champion) {
+                    entry.win_title += 1.0;
+                }
+            }
+        }
+
+        let n = f64::from(iterations);
+        self.standings
+            .iter()
+            .filter_map(|row| odds.remove(&row.roster_id))
+            .map(|odds| PlayoffOdds {
+                make_playoffs: odds.make_playoffs / n,
+                bye: odds.bye / n,
+                win_title: odds.win_title / n,
+                projected_wins: odds.projected_wins / n,
+                ..odds
+            })
+            .collect()
+    }
+
+    fn simulate_regular_season(
+        &self,
+        rng: &mut StdRng,
+        sample: &mut impl FnMut(u8, &mut StdRng) -> f64,
+    ) -> Vec<SimulatedRoster> {
+        let mut rosters: BTreeMap<u8, SimulatedRoster> = self
+            .standings
+            .iter()
+            .map(|row| {
+                let roster = SimulatedRoster {
+                    roster_id: row.roster_id,
+                    division: row.division,
+                    wins: f64::from(row.record.wins) + f64::from(row.record.ties) / 2.0,
+                    games: f64::from(row.record.games()),
+                    points_for: row.points_for,
+                };
+                (row.roster_id, roster)
+            })
+            .collect();
+
+        let mut weeks: BTreeMap<u8, Vec<&ScheduledGame>> = BTreeMap::new();
+        for game in self.schedule {
+            weeks.entry(game.week).or_default().push(game);
+        }
+
+        for games in weeks.values() {
+            let mut scores: Vec<(u8, f64)> = Vec::new();
+            for game in games {
+                let ours = sample(game.roster_id, rng);
+                let theirs = sample(game.opponent, rng);
+                scores.push((game.roster_id, ours));
+                scores.push((game.opponent, theirs));
+
+                for (roster_id, pf, pa) in [
+                    (game.roster_id, ours, theirs),
+                    (game.opponent, theirs, ours),
+                ] {
+                    if let Some(roster) = rosters.get_mut(&roster_id) {
+                        roster.record(Outcome::from_points(pf, pa));
+                        roster.points_for += pf;
+                    }
+                }
+            }
+
+            if self.league.settings.league_average_match == 1 {
+                let mut points: Vec<f64> = scores.iter().map(|(_, s)| *s).collect();
+                points.sort_by(f64::total_cmp);
+                let n = points.len();
+                let median = if n % 2 == 1 {
+                    points[n / 2]
+                } else {
+                    (points[n / 2 - 1] + points[n / 2]) / 2.0
+                };
+
+                for (roster_id, score) in scores {
+                    if let Some(roster) = rosters.get_mut(&roster_id) {
+                        roster.record(Outcome::from_points(score, median));
+                    }
+                }
+            }
+        }
+
+        rosters.into_values().collect()
+    }
+
+    /// Playoff rosters, best seed first.
+    fn seed(&self, season: &[SimulatedRoster]) -> Vec<u8> {
+        let mut ranked: Vec<&SimulatedRoster> = season.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.win_pct()
+                .total_cmp(&a.win_pct())
+                .then_with(|| b.points_for.total_cmp(&a.points_for))
+                .then_with(|| a.roster_id.cmp(&b.roster_id))
+        });
+
+        let playoff_teams = usize::from(self.league.settings.playoff_teams).min(ranked.len());
+        let mut seeds: Vec<u8> = Vec::with_capacity(playoff_teams);
+
+        if self.league.settings.divisions.unwrap_or(0) > 1 {
+            for roster in &ranked {
+                let Some(division) = roster.division else {
+                    continue;
+                };
+                let leads_division = ranked
+                    .iter()
+                    .find(|r| r.division == Some(division))
+                    .is_some_and(|leader| leader.roster_id == roster.roster_id);
+
+                if leads_division && seeds.len() < playoff_teams {
+                    seeds.push(roster.roster_id);
+                }
+            }
+        }
+
+        for roster in &ranked {
+            if seeds.len() >= playoff_teams {
+                break;
+            }
+            if !seeds.contains(&roster.roster_id) {
+                seeds.push(roster.roster_id);
+            }
+        }
+
+        seeds
+    }
+
+    /// Play out the bracket and return the champion.
+    fn simulate_playoffs(
+        &self,
+        seeds: &[u8],
+        rng: &mut StdRng,
+        sample: &mut impl FnMut(u8, &mut StdRng) -> f64,
+    ) -> Option<u8> {
+        if seeds.is_empty() {
+            return None;
+        }
+
+        let reseed = self.league.settings.playoff_seed_type == 1;
+        let rounds = bracket_rounds(seeds.len());
+
+        // Seed numbers (1-based) in bracket order, with byes as seeds past the field
+        let mut alive: Vec<usize> = bracket_order(1 << rounds);
+        let mut round = 0;
+
+        while alive.len() > 1 {
+            let is_final = alive.len() == 2;
+            let weeks = match self.league.settings.playoff_round_type {
+                1 if is_final => 2,
+                2 => 2,
+                _ => 1,
+            };
+
+            if reseed && round > 0 {
+                alive.sort_unstable();
+                let n = alive.len();
+                alive = (0..n / 2)
+                    .flat_map(|i| [alive[i], alive[n - 1 - i]])
+                    .collect();
+            }
+
+            alive = alive
+                .chunks(2)
+                .map(|pair| {
+                    let (a, b) = (pair[0], pair[1]);
+                    if b > seeds.len() {
+                        return a;
+                    }
+                    if a > seeds.len() {
+                        return b;
+                    }
+
+                    let mut score = |seed: usize| -> f64 {
+                        (0..weeks).map(|_| sample(seeds[seed - 1], rng)).sum()
+                    };
+                    let (score_a, score_b) = (score(a), score(b));
+                    match Outcome::from_points(score_a, score_b) {
+                        Outcome::Win => a,
+                        Outcome::Loss => b,
+                        // The higher seed advances on a tie
+                        Outcome::Tie => a.min(b),
+                    }
+                })
+                .collect();
+            round += 1;
+        }
+
+        alive.first().map(|seed| seeds[seed - 1])
+    }
+}
+
+fn bracket_rounds(teams: usize) -> u32 {
+    teams.next_power_of_two().trailing_zeros()
+}
+
+/// How many of the top seeds skip the first round.
+fn bye_count(teams: usize) -> usize {
+    if teams <= 1 {
+        return 0;
+    }
+    teams.next_power_of_two() - teams
+}
+
+/// Seeds in the order they meet in a standard bracket of `size`,
+/// e.g. `[1, 8, 4, 5, 2, 7, 3, 6]`.
+fn bracket_order(size: usize) -> Vec<usize> {
+    let mut order = vec![1];
+    while order.len() < size {
+        let n = order.len() * 2;
+        order = order
+            .iter()
+            .flat_map(|&seed| [seed, n + 1 - seed])
+            .collect();
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standings::standings;
+    use crate::test_support::{league, matchup, rosters, season};
+
+    #[test]
+    fn test_bracket_helpers() {
+        assert_eq!(bracket_order(8), vec![1, 8, 4, 5, 2, 7, 3, 6]);
+        assert_eq!(bye_count(6), 2);
+        assert_eq!(bye_count(4), 0);
+        assert_eq!(bye_count(7), 1);
+        assert_eq!(bracket_rounds(6), 3);
+    }
+
+    #[test]
+    fn test_remaining_schedule_and_distributions() {
+        let league = league(15, 0);
+        let matchups = season();
+
+        let schedule = remaining_schedule(&league, &matchups);
+        assert_eq!(
+            schedule,
+            vec![
+                ScheduledGame {
+                    week: 4,
+                    roster_id: 1,
+                    opponent: 2
+                },
+                ScheduledGame {
+                    week: 4,
+                    roster_id: 3,
+                    opponent: 4
+                },
+            ]
+        );
+
+        let distributions = score_distributions(&league, &matchups);
+        assert_eq!(distributions[&1].mean, 100.0);
+        assert!((distributions[&1].std_dev - 8.165).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_simulated_odds_are_consistent() {
+        let mut league = league(15, 0);
+        league.settings.playoff_teams = 3;
+        let mut matchups = season();
+        matchups.insert(
+            5,
+            vec![
+                matchup(1, 1, 0.0),
+                matchup(3, 1, 0.0),
+                matchup(2, 2, 0.0),
+                matchup(4, 2, 0.0),
+            ],
+        );
+
+        let rosters = rosters();
+        let rows = standings(&league, &rosters, &matchups);
+        let schedule = remaining_schedule(&league, &matchups);
+        let distributions = score_distributions(&league, &matchups);
+
+        let simulator = PlayoffSimulator::new(&league, &rows, &schedule, &distributions);
+        let config = SimulationConfig {
+            iterations: 2_000,
+            seed: 7,
+        };
+        let odds = simulator.run(config);
+        assert_eq!(odds, simulator.run(config));
+
+        let total = |f: fn(&PlayoffOdds) -> f64| odds.iter().map(f).sum::<f64>();
+        assert!((total(|o| o.make_playoffs) - 3.0).abs() < 1e-9);
+        assert!((total(|o| o.bye) - 1.0).abs() < 1e-9);
+        assert!((total(|o| o.win_title) - 1.0).abs() < 1e-9);
+
+        for odds in &odds {
+            assert!(odds.bye <= odds.make_playoffs);
+            assert!(odds.win_title <= odds.make_playoffs);
+            assert!(odds.projected_wins >= 1.0 && odds.projected_wins <= 4.0);
+        }
+    }
+
+    #[test]
+    fn test_completed_season_is_decided_by_standings() {
+        let mut league = league(15, 0);
+        league.settings.playoff_teams = 2;
+
+        let rosters = rosters();
+        let matchups = season();
+        let rows = standings(&league, &rosters, &matchups);
+        let distributions = score_distributions(&league, &matchups);
+
+        let odds = PlayoffSimulator::new(&league, &rows, &[], &distributions)
+            .run(SimulationConfig::default());
+
+        let made: Vec<u8> = odds
+            .iter()
+            .filter(|o| o.make_playoffs == 1.0)
+            .map(|o| o.roster_id)
+            .collect();
+        assert_eq!(made, vec![4, 1]);
+        assert!(odds.iter().all(|o| o.bye == 0.0));
+    }
+}
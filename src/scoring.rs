@@ -0,0 +1,322 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::*;
+
+/// Stats which are scored in ranges ("buckets") rather than per unit.
+/// The first two also come as a raw total (`pts_allow`, `yds_allow`)
+/// from which the bucket can be worked out.
+const BUCKETED_STATS: [&str; 4] = ["pts_allow", "yds_allow", "fgm", "fgmiss"];
+
+/// Applies a league's `ScoringSettings` to raw stat lines.
+#[derive(Clone, Debug)]
+pub struct Scorer {
+    rules: BTreeMap<String, f64>,
+}
+
+/// The fantasy points a stat line is worth, and where they came from.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ScoredLine {
+    /// Total points, rounded to two decimal places like Sleeper's `players_points`.
+    pub points: f64,
+    /// One entry per stat that was worth a non-zero number of points.
+    pub breakdown: Vec<StatPoints>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct StatPoints {
+    pub stat: String,
+    pub value: f64,
+    pub multiplier: f64,
+    pub points: f64,
+}
+
+impl Scorer {
+    pub fn new(settings: &ScoringSettings) -> Self {
+        let rules = settings
+            .to_map()
+            .into_iter()
+            .filter(|(_, multiplier)| *multiplier != 0.0)
+            .map(|(stat, multiplier)| (stat, clean_multiplier(multiplier)))
+            .collect();
+
+        Scorer { rules }
+    }
+
+    pub fn multiplier(&self, stat: &str) -> f64 {
+        self.rules.get(stat).copied().unwrap_or(0.0)
+    }
+
+    /// Score a single player's stat line.
+    pub fn score(&self, stats: &StatLine) -> ScoredLine {
+        let mut total = 0.0;
+        let mut breakdown = Vec::new();
+
+        for (stat, multiplier) in &self.rules {
+            let value = match Bucket::parse(stat) {
+                Some(bucket) => bucket.value(stat, stats),
+                None => stats.get(stat),
+            };
+
+            if value == 0.0 {
+                continue;
+            }
+
+            let points = value * multiplier;
+            total += points;
+            breakdown.push(StatPoints {
+                stat: stat.clone(),
+                value,
+                multiplier: *multiplier,
+                points: round_points(points),
+            });
+        }
+
+        ScoredLine {
+            points: round_points(total),
+            breakdown,
+        }
+    }
+
+    /// Just the points of `score`.
+    pub fn points(&self, stats: &StatLine) -> f64 {
+        self.score(stats).points
+    }
+}
+
+impl ScoringSettings {
+    /// Shorthand for scoring a single stat line with `Scorer`.
+    pub fn score(&self, stats: &StatLine) -> ScoredLine {
+        Scorer::new(self).score(stats)
+    }
+}
+
+/// A ranged stat key such as `pts_allow_7_13`, `pts_allow_0` or `fgm_50p`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Bucket<'a> {
+    stat: &'a str,
+    low: u32,
+    high: Option<u32>,
+}
+
+impl<'a> Bucket<'a> {
+    fn parse(key: &'a str) -> Option<Bucket<'a>> {
+        let stat = BUCKETED_STATS
+            .iter()
+            .filter(|stat| key.starts_with(&format!("{}_", stat)))
+            .max_by_key(|stat| stat.len())?;
+        let range = &key[stat.len() + 1..];
+
+        let (low, high) = match range.split_once('_') {
+            Some((low, high)) => (low.parse().ok()?, Some(high.parse().ok()?)),
+            None => match range.strip_suffix('p') {
+                Some(low) => (low.parse().ok()?, None),
+                None => {
+                    let exact = range.parse().ok()?;
+                    (exact, Some(exact))
+                }
+            },
+        };
+
+        Some(Bucket { stat, low, high })
+    }
+
+    fn contains(&self, value: f64) -> bool {
+        value >= f64::from(self.low) && self.high.is_none_or(|high| value <= f64::from(high))
+    }
+
+    fn covers(&self, other: &Bucket) -> bool {
+        let high_ok = match (self.high, other.high) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(high), Some(other_high)) => other_high <= high,
+        };
+        self.stat == other.stat && other.low >= self.low && high_ok
+    }
+
+    /// How many times this bucket was hit. Taken from the stat line directly
+    /// when present, otherwise worked out from the raw total (points allowed)
+    /// or from the narrower buckets it covers (`fgm_50_59` and `fgm_60p` for `fgm_50p`).
+    fn value(&self, key: &str, stats: &StatLine) -> f64 {
+        if stats.contains(key) {
+            return stats.get(key);
+        }
+
+        let narrower: Vec<(Bucket, f64)> = stats
+            .iter()
+            .filter_map(|(stat, value)| Bucket::parse(stat).map(|b| (b, value)))
+            .filter(|(bucket, _)| bucket.stat == self.stat)
+            .collect();
+
+        if narrower.is_empty() && stats.contains(self.stat) {
+            return if self.contains(stats.get(self.stat)) {
+                1.0
+            } else {
+                0.0
+            };
+        }
+
+        narrower
+            .iter()
+            .filter(|(bucket, _)| self.covers(bucket))
+            .map(|(_, value)| value)
+            .sum()
+    }
+}
+
+/// Sleeper stores multipliers as single precision floats, so 0.04 arrives
+/// as 0.03999999910593033. Recover the value the commissioner typed in.
+fn clean_multiplier(multiplier: f64) -> f64 {
+    format!("{}", multiplier as f32)
+        .parse()
+        .unwrap_or(multiplier)
+}
+
+fn round_points(points: f64) -> f64 {
+    (points * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> ScoringSettings {
+        ScoringSettings {
+            pass_yd: 0.03999999910593033,
+            pass_td: 4.0,
+            pass_int: -1.0,
+            rush_yd: 0.10000000149011612,
+            rush_td: 6.0,
+            rec: 0.5,
+            rec_yd: 0.10000000149011612,
+            rec_td: 6.0,
+            fum_lost: -2.0,
+            sack: 1.0,
+            int: 2.0,
+            pts_allow_0: 10.0,
+            pts_allow_1_6: 7.0,
+            pts_allow_7_13: 4.0,
+            pts_allow_14_20: 1.0,
+            pts_allow_21_27: 0.0,
+            pts_allow_28_34: -1.0,
+            pts_allow_35p: -4.0,
+            xpm: 1.0,
+            fgm_0_19: 3.0,
+            fgm_20_29: 3.0,
+            fgm_30_39: 3.0,
+            fgm_40_49: 4.0,
+            fgm_50p: 5.0,
+            other: [("bonus_rec_te".to_string(), 0.5)].into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_score_offensive_stat_line() {
+        let stats: StatLine = [
+            ("pass_yd", 301.0),
+            ("pass_td", 3.0),
+            ("pass_int", 1.0),
+            ("rush_yd", 27.0),
+            ("fum_lost", 1.0),
+            ("gp", 1.0),
+        ]
+        .into_iter()
+        .collect();
+
+        let scored = settings().score(&stats);
+        assert_eq!(scored.points, 23.74);
+
+        let pass_yd = scored
+            .breakdown
+            .iter()
+            .find(|s| s.stat == "pass_yd")
+            .unwrap();
+        assert_eq!(pass_yd.multiplier, 0.04);
+        assert_eq!(pass_yd.points, 12.04);
+        assert!(scored.breakdown.iter().all(|s| s.stat != "gp"));
+    }
+
+    #[test]
+    fn test_score_other_settings_keys() {
+        let stats: StatLine = [("rec", 5.0), ("rec_yd", 48.0), ("bonus_rec_te", 5.0)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(Scorer::new(&settings()).points(&stats), 9.8);
+    }
+
+    #[test]
+    fn test_score_defense_points_allowed_tiers() {
+        let scorer = Scorer::new(&settings());
+
+        let raw: StatLine = [("pts_allow", 10.0), ("sack", 3.0), ("int", 1.0)]
+            .into_iter()
+            .collect();
+        assert_eq!(scorer.points(&raw), 9.0);
+
+        let shutout: StatLine = [("pts_allow", 0.0)].into_iter().collect();
+        assert_eq!(scorer.points(&shutout), 10.0);
+
+        let blowout: StatLine = [("pts_allow", 42.0)].into_iter().collect();
+        assert_eq!(scorer.points(&blowout), -4.0);
+
+        // Sleeper's own stat lines flag the tier, which takes precedence
+        let flagged: StatLine = [("pts_allow", 10.0), ("pts_allow_14_20", 1.0)]
+            .into_iter()
+            .collect();
+        assert_eq!(scorer.points(&flagged), 1.0);
+    }
+
+    #[test]
+    fn test_score_kicker_distance_buckets() {
+        let stats: StatLine = [
+            ("xpm", 3.0),
+            ("fgm_30_39", 1.0),
+            ("fgm_50_59", 1.0),
+            ("fgm_60p", 1.0),
+        ]
+        .into_iter()
+        .collect();
+
+        let scored = settings().score(&stats);
+        assert_eq!(scored.points, 16.0);
+        let long = scored
+            .breakdown
+            .iter()
+            .find(|s| s.stat == "fgm_50p")
+            .unwrap();
+        assert_eq!(long.value, 2.0);
+    }
+
+    #[test]
+    fn test_parse_buckets() {
+        assert_eq!(
+            Bucket::parse("pts_allow_7_13"),
+            Some(Bucket {
+                stat: "pts_allow",
+                low: 7,
+                high: Some(13)
+            })
+        );
+        assert_eq!(
+            Bucket::parse("fgm_50p"),
+            Some(Bucket {
+                stat: "fgm",
+                low: 50,
+                high: None
+            })
+        );
+        assert_eq!(
+            Bucket::parse("pts_allow_0"),
+            Some(Bucket {
+                stat: "pts_allow",
+                low: 0,
+                high: Some(0)
+            })
+        );
+        assert_eq!(Bucket::parse("fgm_yds"), None);
+        assert_eq!(Bucket::parse("pass_yd"), None);
+    }
+}
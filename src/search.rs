@@ -0,0 +1,367 @@
+use std::cmp::Ordering;
+
+use crate::data::*;
+
+/// Name matches scoring below this are dropped from search results.
+const MIN_FUZZY_SIMILARITY: f64 = 0.6;
+
+/// An in-memory index over `AllPlayers::NFL` for looking players up the way
+/// people type them: partial names ("mahom"), typos ("mahomse") or initials ("ARSB").
+pub struct PlayerIndex<'a> {
+    entries: Vec<IndexEntry<'a>>,
+}
+
+struct IndexEntry<'a> {
+    player: &'a NflPlayer,
+    full: String,
+    first: String,
+    last: String,
+    initials: String,
+}
+
+/// A player returned from `PlayerIndex::search`, along with how well it matched.
+/// Scores range from 0 to 1, where 1 is an exact name match.
+#[derive(Clone, Debug)]
+pub struct PlayerMatch<'a> {
+    pub player: &'a NflPlayer,
+    pub score: f64,
+}
+
+/// Criteria for `PlayerIndex::search`. Every criterion left unset matches all players.
+#[derive(Clone, Debug, Default)]
+pub struct PlayerQuery {
+    pub name: Option<String>,
+    pub positions: Vec<Position>,
+    pub teams: Vec<NflTeam>,
+    pub active: Option<bool>,
+    pub injury_statuses: Vec<InjuryStatus>,
+    pub limit: Option<usize>,
+}
+
+impl PlayerQuery {
+    pub fn name(name: &str) -> Self {
+        PlayerQuery {
+            name: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_position(mut self, position: Position) -> Self {
+        self.positions.push(position);
+        self
+    }
+
+    pub fn with_team(mut self, team: NflTeam) -> Self {
+        self.teams.push(team);
+        self
+    }
+
+    pub fn active(mut self, active: bool) -> Self {
+        self.active = Some(active);
+        self
+    }
+
+    pub fn with_injury_status(mut self, status: InjuryStatus) -> Self {
+        self.injury_statuses.push(status);
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn accepts(&self, player: &NflPlayer) -> bool {
+        let position_ok = self.positions.is_empty()
+            || self.positions.iter().any(|pos| {
+                player.position.as_ref() == Some(pos)
+                    || player
+                        .fantasy_positions
+                        .as_ref()
+                        .is_some_and(|fantasy| fantasy.contains(pos))
+            });
+        let team_ok =
+            self.teams.is_empty() || player.team.as_ref().is_some_and(|t| self.teams.contains(t));
+        let active_ok = self.active.is_none_or(|active| player.active == active);
+        let injury_ok =
+            self.injury_statuses.is_empty() || self.injury_statuses.contains(&player.injury_status);
+
+        position_ok && team_ok && active_ok && injury_ok
+    }
+}
+
+impl<'a> PlayerIndex<'a> {
+    /// Index every player of `AllPlayers::NFL`. Other sports produce an empty index.
+    pub fn new(players: &'a AllPlayers) -> Self {
+        match players {
+            AllPlayers::NFL(players) => Self::from_players(players.values()),
+            AllPlayers::LCS(_) | AllPlayers::NBA(_) => PlayerIndex {
+                entries: Vec::new(),
+            },
+        }
+    }
+
+    pub fn from_players(players: impl IntoIterator<Item = &'a NflPlayer>) -> Self {
+        let entries = players
+            .into_iter()
+            .map(|player| {
+                let display = player
+                    .full_name
+                    .clone()
+                    .unwrap_or_else(|| format!("{} {}", player.first_name, player.last_name));
+                let full = match &player.search_full_name {
+                    Some(name) if !name.is_empty() => normalize(name),
+                    _ => normalize(&display),
+                };
+
+                IndexEntry {
+                    player,
+                    full,
+                    first: normalize(&player.first_name),
+                    last: normalize(&player.last_name),
+                    initials: initials(&display),
+                }
+            })
+            .collect();
+
+        PlayerIndex { entries }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Shorthand for a name-only `search`.
+    pub fn search_name(&self, name: &str) -> Vec<PlayerMatch<'a>> {
+        self.search(&PlayerQuery::name(name))
+    }
+
+    /// Players matching `query`, best match first. Equal scores are ordered by
+    /// Sleeper's `search_rank`, so the more relevant fantasy player wins ties.
+    pub fn search(&self, query: &PlayerQuery) -> Vec<PlayerMatch<'a>> {
+        let name = query
+            .name
+            .as_deref()
+            .map(normalize)
+            .filter(|n| !n.is_empty());
+
+        let mut matches: Vec<PlayerMatch<'a>> = self
+            .entries
+            .iter()
+            .filter(|entry| query.accepts(entry.player))
+            .filter_map(|entry| {
+                let score = match &name {
+                    Some(name) => entry.score(name)?,
+                    None => 1.0,
+                };
+                Some(PlayerMatch {
+                    player: entry.player,
+                    score,
+                })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| search_rank(a.player).cmp(&search_rank(b.player)))
+                .then_with(|| a.player.player_id.cmp(&b.player.player_id))
+        });
+
+        if let Some(limit) = query.limit {
+            matches.truncate(limit);
+        }
+
+        matches
+    }
+}
+
+impl IndexEntry<'_> {
+    fn score(&self, query: &str) -> Option<f64> {
+        let names = [&self.full, &self.last, &self.first];
+
+        if names.iter().any(|name| *name == query) {
+            return Some(1.0);
+        }
+        if names.iter().any(|name| name.starts_with(query)) {
+            return Some(0.9);
+        }
+        if query.len() >= 2 && self.initials == query {
+            return Some(0.85);
+        }
+        if self.full.contains(query) {
+            return Some(0.75);
+        }
+
+        // Compare against the same-length prefix too, so a typo in a partial
+        // name ("mahmoe") is still found.
+        let similarity = names
+            .iter()
+            .flat_map(|name| {
+                let prefix: String = name.chars().take(query.chars().count()).collect();
+                [similarity(query, name), similarity(query, &prefix)]
+            })
+            .fold(0.0, f64::max);
+
+        (similarity >= MIN_FUZZY_SIMILARITY).then_some(0.7 * similarity)
+    }
+}
+
+fn search_rank(player: &NflPlayer) -> u64 {
+    player.search_rank.unwrap_or(u64::MAX)
+}
+
+/// Lowercase and strip everything but letters and digits, the same shape
+/// Sleeper uses for `search_full_name`.
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// "Amon-Ra St. Brown" => "arsb"
+fn initials(name: &str) -> String {
+    name.split(|c: char| c.is_whitespace() || c == '-')
+        .filter_map(|part| part.chars().find(|c| c.is_alphanumeric()))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Levenshtein distance scaled to 0..=1 by the longer string's length.
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(id: &str, first: &str, last: &str, extra: serde_json::Value) -> NflPlayer {
+        let mut value = serde_json::json!({
+            "player_id": id,
+            "first_name": first,
+            "last_name": last,
+            "full_name": format!("{} {}", first, last),
+            "search_full_name": normalize(&format!("{}{}", first, last)),
+            "sport": "nfl",
+            "active": true,
+        });
+        for (key, field) in extra.as_object().unwrap() {
+            value[key] = field.clone();
+        }
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn players() -> Vec<NflPlayer> {
+        vec![
+            player(
+                "4046",
+                "Patrick",
+                "Mahomes",
+                serde_json::json!({ "position": "QB", "team": "KC", "search_rank": 10 }),
+            ),
+            player(
+                "7547",
+                "Amon-Ra",
+                "St. Brown",
+                serde_json::json!({ "position": "WR", "team": "DET", "search_rank": 20 }),
+            ),
+            player(
+                "9999",
+                "Patrick",
+                "Mahomes Sr.",
+                serde_json::json!({ "position": "P", "active": false, "search_rank": 9999999 }),
+            ),
+            player(
+                "6794",
+                "Justin",
+                "Jefferson",
+                serde_json::json!({
+                    "position": "WR",
+                    "team": "MIN",
+                    "search_rank": 5,
+                    "injury_status": "Out"
+                }),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_search_by_partial_name_and_typos() {
+        let players = players();
+        let index = PlayerIndex::from_players(&players);
+
+        let results = index.search_name("mahomes");
+        assert_eq!(results[0].player.player_id, "4046");
+        assert_eq!(results[0].score, 1.0);
+        assert_eq!(results[1].player.player_id, "9999");
+
+        let results = index.search_name("Mahomse");
+        assert_eq!(results[0].player.player_id, "4046");
+
+        let results = index.search_name("jeff");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].player.player_id, "6794");
+
+        assert!(index.search_name("zzzzzz").is_empty());
+    }
+
+    #[test]
+    fn test_search_by_initials() {
+        let players = players();
+        let index = PlayerIndex::from_players(&players);
+
+        let results = index.search_name("ARSB");
+        assert_eq!(results[0].player.player_id, "7547");
+    }
+
+    #[test]
+    fn test_search_filters() {
+        let players = players();
+        let index = PlayerIndex::from_players(&players);
+
+        let results = index.search(&PlayerQuery::name("patrick").active(true));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].player.player_id, "4046");
+
+        let results = index.search(&PlayerQuery::default().with_position(Position::WR));
+        let ids: Vec<&str> = results
+            .iter()
+            .map(|m| m.player.player_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["6794", "7547"]);
+
+        let results = index.search(&PlayerQuery::default().with_team(NflTeam::DET));
+        assert_eq!(results.len(), 1);
+
+        let results = index.search(&PlayerQuery::default().with_injury_status(InjuryStatus::Out));
+        assert_eq!(results[0].player.player_id, "6794");
+
+        let results = index.search(&PlayerQuery::default().limit(2));
+        assert_eq!(results.len(), 2);
+    }
+}
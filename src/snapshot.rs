@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::*;
+
+/// A league with its users, rosters and matchups, fetched together and joined
+/// so callers don't have to match `owner_id`s and `roster_id`s by hand.
+///
+/// Saved as a single JSON file, a snapshot is enough to run standings,
+/// analytics and reports offline.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeagueSnapshot {
+    pub league: League,
+    pub users: Vec<SleeperUser>,
+    pub rosters: Vec<Roster>,
+    /// Keyed by week.
+    pub matchups: BTreeMap<u8, Vec<Matchup>>,
+    /// Only the players found on `rosters`. Empty until `attach_players` is
+    /// called, since `Client::get_league_snapshot` does not fetch players.
+    #[serde(default)]
+    pub players: BTreeMap<PlayerId, NflPlayer>,
+}
+
+/// One roster joined with its owner and players.
+#[derive(Clone, Debug)]
+pub struct Team<'a> {
+    pub roster: &'a Roster,
+    pub owner: Option<&'a SleeperUser>,
+    /// Players Sleeper knows nothing about (or which weren't attached) are left out.
+    pub players: Vec<&'a NflPlayer>,
+}
+
+impl<'a> Team<'a> {
+    /// The owner's team name, falling back to their display name.
+    pub fn name(&self) -> Option<&'a str> {
+        let owner = self.owner?;
+        owner.team_name().or(owner.display_name.as_deref())
+    }
+}
+
+impl LeagueSnapshot {
+    pub fn new(
+        league: League,
+        users: Vec<SleeperUser>,
+        rosters: Vec<Roster>,
+        matchups: BTreeMap<u8, Vec<Matchup>>,
+    ) -> Self {
+        LeagueSnapshot {
+            league,
+            users,
+            rosters,
+            matchups,
+            players: BTreeMap::new(),
+        }
+    }
+
+    /// Keep the entries of `players` which appear on any roster. Players
+    /// from other sports are ignored.
+    pub fn attach_players(&mut self, players: &AllPlayers) {
+        let AllPlayers::NFL(players) = players else {
+            return;
+        };
+
+        for player_id in self.rosters.iter().flat_map(|r| &r.players) {
+            if let Some(player) = players.get(player_id) {
+                self.players.insert(player_id.clone(), player.clone());
+            }
+        }
+    }
+
+    pub fn roster(&self, roster_id: u8) -> Option<&Roster> {
+        self.rosters.iter().find(|r| r.roster_id == roster_id)
+    }
+
+    pub fn user(&self, user_id: &str) -> Option<&SleeperUser> {
+        self.users.iter().find(|u| u.user_id == user_id)
+    }
+
+    pub fn owner(&self, roster_id: u8) -> Option<&SleeperUser> {
+        self.user(self.roster(roster_id)?.owner_id.as_deref()?)
+    }
+
+    /// The roster owned by `user_id`.
+    pub fn roster_of(&self, user_id: &str) -> Option<&Roster> {
+        self.rosters
+            .iter()
+            .find(|r| r.owner_id.as_deref() == Some(user_id))
+    }
+
+    pub fn team(&self, roster_id: u8) -> Option<Team<'_>> {
+        self.roster(roster_id).map(|roster| self.join(roster))
+    }
+
+    /// Every roster joined with its owner and players, ordered by `roster_id`.
+    pub fn teams(&self) -> Vec<Team<'_>> {
+        let mut teams: Vec<Team> = self.rosters.iter().map(|r| self.join(r)).collect();
+        teams.sort_by_key(|t| t.roster.roster_id);
+        teams
+    }
+
+    /// Shorthand for the name of `team(roster_id)`.
+    pub fn team_name(&self, roster_id: u8) -> Option<&str> {
+        self.team(roster_id)?.name()
+    }
+
+    pub fn player(&self, player_id: &str) -> Option<&NflPlayer> {
+        self.players.get(player_id)
+    }
+
+    pub fn matchups(&self, week: u8) -> &[Matchup] {
+        self.matchups
+            .get(&week)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn write_json<W: Write>(&self, writer: W) -> Result<(), SleeperError> {
+        serde_json::to_writer(writer, self).map_err(|e| SleeperError::ExportError(e.to_string()))
+    }
+
+    pub fn read_json<R: Read>(reader: R) -> Result<Self, SleeperError> {
+        serde_json::from_reader(reader)
+            .map_err(|e| SleeperError::DeserializationError(format!("LeagueSnapshot: {}", e)))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SleeperError> {
+        let file = File::create(path).map_err(|e| SleeperError::IoError(e.to_string()))?;
+        let mut writer = BufWriter::new(file);
+        self.write_json(&mut writer)?;
+        writer
+            .flush()
+            .map_err(|e| SleeperError::IoError(e.to_string()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SleeperError> {
+        let file = File::open(path).map_err(|e| SleeperError::IoError(e.to_string()))?;
+        Self::read_json(BufReader::new(file))
+    }
+
+    fn join<'a>(&'a self, roster: &'a Roster) -> Team<'a> {
+        Team {
+            roster,
+            owner: roster.owner_id.as_deref().and_then(|id| self.user(id)),
+            players: roster
+                .players
+                .iter()
+                .filter_map(|id| self.players.get(id))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::snapshot;
+
+    #[test]
+    fn test_joined_views() {
+        let snapshot = snapshot();
+
+        assert_eq!(snapshot.players.len(), 2);
+        assert_eq!(snapshot.team_name(1), Some("Gridiron"));
+        assert_eq!(snapshot.team_name(2), Some("Bob"));
+        assert_eq!(snapshot.roster_of("owner2").unwrap().roster_id, 2);
+
+        let team = snapshot.team(1).unwrap();
+        assert_eq!(team.owner.unwrap().user_id, "owner1");
+        let names: Vec<&str> = team
+            .players
+            .iter()
+            .filter_map(|p| p.full_name.as_deref())
+            .collect();
+        assert_eq!(names, vec!["Patrick Mahomes"]);
+
+        assert_eq!(snapshot.teams().len(), 2);
+        assert_eq!(snapshot.matchups(1).len(), 2);
+        assert!(snapshot.matchups(2).is_empty());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let snapshot = snapshot();
+
+        let mut json = Vec::new();
+        snapshot.write_json(&mut json).unwrap();
+        let restored = LeagueSnapshot::read_json(json.as_slice()).unwrap();
+
+        assert_eq!(restored.league.league_id, snapshot.league.league_id);
+        assert_eq!(restored.league.season, snapshot.league.season);
+        assert_eq!(restored.team_name(1), Some("Gridiron"));
+        assert_eq!(restored.matchups(1)[1].points, 90.0);
+        assert_eq!(restored.player("6794").unwrap().team, Some(NflTeam::MIN));
+    }
+
+    #[test]
+    fn test_load_reports_missing_files_as_io_errors() {
+        let path = std::env::temp_dir().join("sleeper-snapshot-does-not-exist.json");
+
+        assert!(matches!(
+            LeagueSnapshot::load(path),
+            Err(SleeperError::IoError(_))
+        ));
+    }
+}
@@ -0,0 +1,826 @@
+use std::path::Path;
+
+use futures::stream::{self, StreamExt, TryStreamExt};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::data::*;
+use crate::export::{matchup_rows, player_rows, roster_rows};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS leagues (
+    league_id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    season INTEGER NOT NULL,
+    season_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    sport TEXT NOT NULL,
+    total_rosters INTEGER NOT NULL,
+    previous_league_id TEXT,
+    start_week INTEGER NOT NULL,
+    playoff_week_start INTEGER NOT NULL,
+    current_week INTEGER NOT NULL,
+    json TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS users (
+    league_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    username TEXT,
+    display_name TEXT,
+    team_name TEXT,
+    avatar TEXT,
+    is_owner INTEGER NOT NULL,
+    is_bot INTEGER NOT NULL,
+    PRIMARY KEY (league_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS rosters (
+    league_id TEXT NOT NULL,
+    roster_id INTEGER NOT NULL,
+    owner_id TEXT,
+    division INTEGER,
+    wins INTEGER NOT NULL,
+    losses INTEGER NOT NULL,
+    ties INTEGER NOT NULL,
+    points_for REAL NOT NULL,
+    points_against REAL NOT NULL,
+    waiver_position INTEGER NOT NULL,
+    waiver_budget_used INTEGER NOT NULL,
+    total_moves INTEGER NOT NULL,
+    PRIMARY KEY (league_id, roster_id)
+);
+
+CREATE TABLE IF NOT EXISTS roster_players (
+    league_id TEXT NOT NULL,
+    roster_id INTEGER NOT NULL,
+    player_id TEXT NOT NULL,
+    slot TEXT NOT NULL,
+    PRIMARY KEY (league_id, roster_id, player_id)
+);
+
+CREATE TABLE IF NOT EXISTS matchups (
+    league_id TEXT NOT NULL,
+    week INTEGER NOT NULL,
+    roster_id INTEGER NOT NULL,
+    matchup_id INTEGER,
+    points REAL NOT NULL,
+    custom_points REAL,
+    PRIMARY KEY (league_id, week, roster_id)
+);
+
+CREATE TABLE IF NOT EXISTS matchup_players (
+    league_id TEXT NOT NULL,
+    week INTEGER NOT NULL,
+    roster_id INTEGER NOT NULL,
+    player_id TEXT NOT NULL,
+    points REAL NOT NULL,
+    started INTEGER NOT NULL,
+    PRIMARY KEY (league_id, week, roster_id, player_id)
+);
+
+CREATE TABLE IF NOT EXISTS transactions (
+    transaction_id TEXT PRIMARY KEY,
+    league_id TEXT NOT NULL,
+    week INTEGER NOT NULL,
+    type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created INTEGER NOT NULL,
+    status_updated INTEGER NOT NULL,
+    creator TEXT,
+    waiver_bid INTEGER,
+    roster_ids TEXT NOT NULL,
+    adds TEXT,
+    drops TEXT,
+    draft_picks TEXT NOT NULL,
+    waiver_budget TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS drafts (
+    draft_id TEXT PRIMARY KEY,
+    league_id TEXT,
+    season INTEGER NOT NULL,
+    type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    start_time INTEGER,
+    rounds INTEGER,
+    teams INTEGER,
+    json TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS picks (
+    draft_id TEXT NOT NULL,
+    pick_no INTEGER NOT NULL,
+    round INTEGER NOT NULL,
+    draft_slot INTEGER NOT NULL,
+    roster_id INTEGER,
+    picked_by TEXT,
+    player_id TEXT NOT NULL,
+    is_keeper INTEGER,
+    amount INTEGER,
+    PRIMARY KEY (draft_id, pick_no)
+);
+
+CREATE TABLE IF NOT EXISTS sync_state (
+    league_id TEXT PRIMARY KEY,
+    last_synced_week INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS players (
+    player_id TEXT PRIMARY KEY,
+    full_name TEXT,
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    position TEXT,
+    fantasy_positions TEXT,
+    team TEXT,
+    number INTEGER,
+    status TEXT,
+    active INTEGER NOT NULL,
+    injury_status TEXT,
+    injury_body_part TEXT,
+    injury_notes TEXT,
+    injury_start_date TEXT,
+    practice_participation TEXT,
+    practice_description TEXT,
+    depth_chart_position TEXT,
+    depth_chart_order INTEGER,
+    search_rank INTEGER,
+    age INTEGER,
+    birth_date TEXT,
+    years_exp INTEGER,
+    height_inches INTEGER,
+    weight_lbs INTEGER,
+    college TEXT,
+    high_school TEXT,
+    birth_city TEXT,
+    birth_state TEXT,
+    birth_country TEXT,
+    news_updated INTEGER,
+    espn_id INTEGER,
+    yahoo_id INTEGER,
+    sportradar_id TEXT,
+    gsis_id TEXT,
+    rotowire_id INTEGER,
+    rotoworld_id INTEGER,
+    stats_id INTEGER,
+    swish_id INTEGER,
+    fantasy_data_id INTEGER
+);
+";
+
+/// What one `Database::sync_league` call fetched.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SyncReport {
+    pub league_id: LeagueId,
+    /// The weeks whose matchups and transactions were (re)fetched.
+    pub weeks: Vec<u8>,
+    pub transactions: usize,
+    pub draft_picks: usize,
+}
+
+/// League data in a normalized SQLite schema, for running SQL over seasons of history.
+///
+/// Every write is an upsert keyed on Sleeper's own IDs, so saving the same
+/// data twice leaves the database unchanged.
+pub struct Database {
+    connection: Connection,
+}
+
+impl Database {
+    /// Open (or create) the database at `path`, creating any missing tables.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SleeperError> {
+        Self::init(Connection::open(path).map_err(db_error)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, SleeperError> {
+        Self::init(Connection::open_in_memory().map_err(db_error)?)
+    }
+
+    fn init(connection: Connection) -> Result<Self, SleeperError> {
+        connection.execute_batch(SCHEMA).map_err(db_error)?;
+        Ok(Database { connection })
+    }
+
+    /// For running queries.
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    pub fn upsert_league(&mut self, league: &League) -> Result<(), SleeperError> {
+        self.write(|tx| upsert_league(tx, league))
+    }
+
+    pub fn upsert_users(&mut self, users: &[SleeperUser]) -> Result<(), SleeperError> {
+        self.write(|tx| upsert_users(tx, users))
+    }
+
+    /// Replaces each roster's players, so players who have since left are removed.
+    pub fn upsert_rosters(
+        &mut self,
+        league: &League,
+        rosters: &[Roster],
+    ) -> Result<(), SleeperError> {
+        self.write(|tx| upsert_rosters(tx, league, rosters))
+    }
+
+    /// Replaces each matchup's players, like `upsert_rosters`.
+    pub fn upsert_matchups(
+        &mut self,
+        league_id: &str,
+        week: u8,
+        matchups: &[Matchup],
+    ) -> Result<(), SleeperError> {
+        self.write(|tx| upsert_matchups(tx, league_id, week, matchups))
+    }
+
+    pub fn upsert_transactions(
+        &mut self,
+        league_id: &str,
+        transactions: &[Transaction],
+    ) -> Result<(), SleeperError> {
+        self.write(|tx| upsert_transactions(tx, league_id, transactions))
+    }
+
+    pub fn upsert_draft(&mut self, draft: &Draft, picks: &[DraftPick]) -> Result<(), SleeperError> {
+        self.write(|tx| upsert_draft(tx, draft, picks))
+    }
+
+    /// Only NFL players can be saved; other sports produce an `ExportError`.
+    pub fn upsert_players(&mut self, players: &AllPlayers) -> Result<(), SleeperError> {
+        let rows = player_rows(players)?;
+        self.write(|tx| {
+            let mut statement = tx.prepare(
+                "INSERT OR REPLACE INTO players VALUES
+                 (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                 ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32,
+                 ?33, ?34, ?35, ?36, ?37, ?38, ?39)",
+            )?;
+            for row in &rows {
+                statement.execute(params![
+                    row.player_id,
+                    row.full_name,
+                    row.first_name,
+                    row.last_name,
+                    row.position,
+                    row.fantasy_positions,
+                    row.team,
+                    row.number,
+                    row.status,
+                    row.active,
+                    row.injury_status,
+                    row.injury_body_part,
+                    row.injury_notes,
+                    row.injury_start_date,
+                    row.practice_participation,
+                    row.practice_description,
+                    row.depth_chart_position,
+                    row.depth_chart_order,
+                    row.search_rank,
+                    row.age,
+                    row.birth_date,
+                    row.years_exp,
+                    row.height_inches,
+                    row.weight_lbs,
+                    row.college,
+                    row.high_school,
+                    row.birth_city,
+                    row.birth_state,
+                    row.birth_country,
+                    row.news_updated,
+                    row.espn_id,
+                    row.yahoo_id,
+                    row.sportradar_id,
+                    row.gsis_id,
+                    row.rotowire_id,
+                    row.rotoworld_id,
+                    row.stats_id,
+                    row.swish_id,
+                    row.fantasy_data_id,
+                ])?;
+            }
+            Ok(())
+        })
+    }
+
+    /// The league's current week as of the last `sync_league`, or `None` if it
+    /// has never been synced. Preseason syncs are recorded as week 0.
+    pub fn last_synced_week(&self, league_id: &str) -> Result<Option<u8>, SleeperError> {
+        self.connection
+            .query_row(
+                "SELECT last_synced_week FROM sync_state WHERE league_id = ?1",
+                [league_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(db_error)
+    }
+
+    /// Fetch a league and save it along with its users, rosters, matchups,
+    /// transactions and drafts.
+    ///
+    /// Syncs are incremental: matchups and transactions are only fetched from
+    /// the last week synced (which may have still been in progress) up to the
+    /// league's current week, and drafts already saved as complete are not
+    /// fetched again. Users and rosters are replaced outright, so those who
+    /// have left the league are removed. Nothing is saved unless every request
+    /// succeeds.
+    pub async fn sync_league(
+        &mut self,
+        client: &Client,
+        league_id: &str,
+    ) -> Result<SyncReport, SleeperError> {
+        let (league, users, rosters, drafts) = futures::try_join!(
+            client.get_league(league_id),
+            client.get_users_in_league(league_id),
+            client.get_rosters(league_id),
+            client.get_league_drafts(league_id),
+        )?;
+
+        let start_week = league.settings.start_week.max(1);
+        let last_synced = self.last_synced_week(league_id)?;
+        let from_week = last_synced.unwrap_or(start_week).max(start_week);
+        let weeks: Vec<u8> = (from_week..=league.settings.leg).collect();
+
+        let mut season = client.get_season_matchups(league_id, weeks.clone()).await;
+        if let Some((_, e)) = season.errors.pop_first() {
+            return Err(e);
+        }
+
+        // Transactions start before the first scoring week, in the preseason
+        let transaction_weeks = last_synced.unwrap_or(1).max(1)..=league.settings.leg;
+        let transactions: Vec<Transaction> = stream::iter(transaction_weeks)
+            .map(|week| client.get_transactions(league_id, week))
+            .buffer_unordered(client.concurrency_limit())
+            .try_concat()
+            .await?;
+
+        let mut draft_picks = Vec::new();
+        for draft in drafts {
+            if !self.is_complete_draft(&draft.draft_id)? {
+                let picks = client.get_draft_picks(&draft.draft_id).await?;
+                draft_picks.push((draft, picks));
+            }
+        }
+
+        self.write(|tx| {
+            upsert_league(tx, &league)?;
+            for table in ["users", "rosters", "roster_players"] {
+                tx.execute(
+                    &format!("DELETE FROM {} WHERE league_id = ?1", table),
+                    [league_id],
+                )?;
+            }
+            upsert_users(tx, &users)?;
+            upsert_rosters(tx, &league, &rosters)?;
+            for (week, matchups) in &season.matchups {
+                upsert_matchups(tx, league_id, *week, matchups)?;
+            }
+            upsert_transactions(tx, league_id, &transactions)?;
+            for (draft, picks) in &draft_picks {
+                upsert_draft(tx, draft, picks)?;
+            }
+            tx.execute(
+                "INSERT OR REPLACE INTO sync_state VALUES (?1, ?2)",
+                params![league_id, league.settings.leg],
+            )?;
+            Ok(())
+        })?;
+
+        Ok(SyncReport {
+            league_id: league_id.to_string(),
+            weeks,
+            transactions: transactions.len(),
+            draft_picks: draft_picks.iter().map(|(_, picks)| picks.len()).sum(),
+        })
+    }
+
+    /// Fetch and save every NFL player, returning how many there were.
+    pub async fn sync_players(&mut self, client: &Client) -> Result<usize, SleeperError> {
+        let players = client.get_all_players(SleeperSport::NFL).await?;
+        self.upsert_players(&players)?;
+
+        match players {
+            AllPlayers::NFL(players) => Ok(players.len()),
+            _ => Ok(0),
+        }
+    }
+
+    fn is_complete_draft(&self, draft_id: &str) -> Result<bool, SleeperError> {
+        let status: Option<String> = self
+            .connection
+            .query_row(
+                "SELECT status FROM drafts WHERE draft_id = ?1",
+                [draft_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(db_error)?;
+
+        Ok(status.map(DraftStatus::from) == Some(DraftStatus::Complete))
+    }
+
+    /// Run `f` in a single SQLite transaction.
+    fn write<F>(&mut self, f: F) -> Result<(), SleeperError>
+    where
+        F: FnOnce(&Connection) -> rusqlite::Result<()>,
+    {
+        let tx = self.connection.transaction().map_err(db_error)?;
+        f(&tx).map_err(db_error)?;
+        tx.commit().map_err(db_error)
+    }
+}
+
+fn upsert_league(tx: &Connection, league: &League) -> rusqlite::Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO leagues VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        params![
+            league.league_id,
+            league.name,
+            league.season.year(),
+            league.season_type.to_string(),
+            league.status,
+            league.sport,
+            league.total_rosters,
+            league.previous_league(),
+            league.settings.start_week,
+            league.settings.playoff_week_start,
+            league.settings.leg,
+            json(league)?,
+        ],
+    )?;
+    Ok(())
+}
+
+fn upsert_users(tx: &Connection, users: &[SleeperUser]) -> rusqlite::Result<()> {
+    let mut statement =
+        tx.prepare("INSERT OR REPLACE INTO users VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)")?;
+    for user in users {
+        statement.execute(params![
+            user.league_id,
+            user.user_id,
+            user.username,
+            user.display_name,
+            user.team_name(),
+            user.avatar,
+            user.is_owner,
+            user.is_bot,
+        ])?;
+    }
+    Ok(())
+}
+
+fn upsert_rosters(tx: &Connection, league: &League, rosters: &[Roster]) -> rusqlite::Result<()> {
+    let mut statement = tx.prepare(
+        "INSERT OR REPLACE INTO rosters VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+    )?;
+    for roster in rosters {
+        let settings = &roster.settings;
+        statement.execute(params![
+            roster.league_id,
+            roster.roster_id,
+            roster.owner_id,
+            settings.division,
+            settings.wins,
+            settings.losses,
+            settings.ties,
+            settings.points_for(),
+            settings.points_against(),
+            settings.waiver_position,
+            settings.waiver_budget_used,
+            settings.total_moves,
+        ])?;
+        tx.execute(
+            "DELETE FROM roster_players WHERE league_id = ?1 AND roster_id = ?2",
+            params![roster.league_id, roster.roster_id],
+        )?;
+    }
+
+    let mut statement =
+        tx.prepare("INSERT OR REPLACE INTO roster_players VALUES (?1, ?2, ?3, ?4)")?;
+    for row in roster_rows(league, &[], rosters) {
+        statement.execute(params![
+            row.league_id,
+            row.roster_id,
+            row.player_id,
+            row.slot
+        ])?;
+    }
+    Ok(())
+}
+
+fn upsert_matchups(
+    tx: &Connection,
+    league_id: &str,
+    week: u8,
+    matchups: &[Matchup],
+) -> rusqlite::Result<()> {
+    let mut statement =
+        tx.prepare("INSERT OR REPLACE INTO matchups VALUES (?1, ?2, ?3, ?4, ?5, ?6)")?;
+    for matchup in matchups {
+        statement.execute(params![
+            league_id,
+            week,
+            matchup.roster_id,
+            matchup.matchup_id,
+            matchup.points,
+            matchup.custom_points,
+        ])?;
+        tx.execute(
+            "DELETE FROM matchup_players WHERE league_id = ?1 AND week = ?2 AND roster_id = ?3",
+            params![league_id, week, matchup.roster_id],
+        )?;
+    }
+
+    let mut statement =
+        tx.prepare("INSERT OR REPLACE INTO matchup_players VALUES (?1, ?2, ?3, ?4, ?5, ?6)")?;
+    let week_matchups = [(week, matchups.to_vec())].into_iter().collect();
+    for row in matchup_rows(&week_matchups) {
+        statement.execute(params![
+            league_id,
+            row.week,
+            row.roster_id,
+            row.player_id,
+            row.points,
+            row.started,
+        ])?;
+    }
+    Ok(())
+}
+
+fn upsert_transactions(
+    tx: &Connection,
+    league_id: &str,
+    transactions: &[Transaction],
+) -> rusqlite::Result<()> {
+    let mut statement = tx.prepare(
+        "INSERT OR REPLACE INTO transactions VALUES
+         (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+    )?;
+    for transaction in transactions {
+        statement.execute(params![
+            transaction.transaction_id,
+            league_id,
+            transaction.leg,
+            transaction.transaction_type.to_string(),
+            transaction.status.to_string(),
+            transaction.created.0,
+            transaction.status_updated.0,
+            transaction.creator,
+            transaction.settings.as_ref().and_then(|s| s.waiver_bid),
+            json(&transaction.roster_ids)?,
+            transaction.adds.as_ref().map(json).transpose()?,
+            transaction.drops.as_ref().map(json).transpose()?,
+            json(&transaction.draft_picks)?,
+            json(&transaction.waiver_budget)?,
+        ])?;
+    }
+    Ok(())
+}
+
+fn upsert_draft(tx: &Connection, draft: &Draft, picks: &[DraftPick]) -> rusqlite::Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO drafts VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            draft.draft_id,
+            draft.league_id,
+            draft.season.year(),
+            draft.draft_type.to_string(),
+            draft.status.to_string(),
+            draft.start_time.map(|t| t.0),
+            draft.rounds(),
+            draft.teams(),
+            json(draft)?,
+        ],
+    )?;
+
+    let mut statement =
+        tx.prepare("INSERT OR REPLACE INTO picks VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)")?;
+    for pick in picks {
+        statement.execute(params![
+            pick.draft_id,
+            pick.pick_no,
+            pick.round,
+            pick.draft_slot,
+            pick.roster_id
+                .or_else(|| draft.roster_for_slot(pick.draft_slot)),
+            pick.picked_by.as_deref().filter(|id| !id.is_empty()),
+            pick.player_id,
+            pick.is_keeper,
+            pick.amount(),
+        ])?;
+    }
+    Ok(())
+}
+
+/// Nested values are stored as JSON text, which SQLite's `json_each` can query.
+fn json<T: Serialize>(value: &T) -> rusqlite::Result<String> {
+    serde_json::to_string(value).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+fn db_error(e: rusqlite::Error) -> SleeperError {
+    SleeperError::DatabaseError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::PlayerRow;
+    use crate::test_support::{roster, snapshot, user};
+
+    fn count(db: &Database, table: &str) -> u32 {
+        db.connection()
+            .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sync_league_is_incremental_and_idempotent() {
+        let mut server = mockito::Server::new_async().await;
+        let mut league: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/fixtures/league.json")).unwrap();
+        league["league_id"] = "1".into();
+        league["settings"]["leg"] = 2.into();
+
+        let mocks = [
+            ("/league/1", league.to_string()),
+            (
+                "/league/1/users",
+                r#"[{"user_id": "u1", "username": "alice", "settings": null,
+                    "metadata": {"team_name": "Gridiron"}, "is_owner": true, "is_bot": false,
+                    "league_id": "1", "display_name": "Alice", "avatar": null}]"#
+                    .to_string(),
+            ),
+            (
+                "/league/1/rosters",
+                r#"[{"taxi": null, "starters": ["4046"], "settings": {"wins": 1, "fpts": 100},
+                    "roster_id": 1, "reserve": ["9999"], "players": ["4046", "6794", "9999"],
+                    "player_map": null, "owner_id": "u1", "metadata": null, "league_id": "1",
+                    "keepers": null, "co_owners": null}]"#
+                    .to_string(),
+            ),
+            (
+                "/league/1/drafts",
+                r#"[{"draft_id": "d1", "league_id": "1", "type": "snake", "status": "complete",
+                    "sport": "nfl", "season": "2023", "season_type": "regular",
+                    "settings": {"rounds": 1, "teams": 1}, "metadata": null,
+                    "draft_order": null, "slot_to_roster_id": {"1": 1}}]"#
+                    .to_string(),
+            ),
+            (
+                "/draft/d1/picks",
+                r#"[{"player_id": "4046", "picked_by": "u1", "roster_id": null, "round": 1,
+                    "draft_slot": 1, "pick_no": 1, "is_keeper": null, "draft_id": "d1",
+                    "metadata": {}}]"#
+                    .to_string(),
+            ),
+            (
+                "/league/1/transactions/1",
+                include_str!("../tests/fixtures/transactions_nfl_week_3.json").to_string(),
+            ),
+            ("/league/1/transactions/2", "[]".to_string()),
+        ];
+        for (path, body) in mocks {
+            server
+                .mock("GET", path)
+                .with_body(body)
+                .create_async()
+                .await;
+        }
+        for week in 1..=2 {
+            server
+                .mock("GET", format!("/league/1/matchups/{}", week).as_str())
+                .with_body(format!(
+                    r#"[{{"starters_points": [20.0], "starters": ["4046"], "roster_id": 1,
+                        "points": {}.5, "players_points": {{"4046": 20.0, "6794": 7.5}},
+                        "players": ["4046", "6794"], "matchup_id": 1, "custom_points": null}}]"#,
+                    week * 10
+                ))
+                .create_async()
+                .await;
+        }
+
+        let client = Client::with_base_url(&server.url());
+        let mut db = Database::open_in_memory().unwrap();
+
+        // A member who has since left the league
+        let mut departed_user = user("u2", "Bob", None);
+        departed_user.league_id = "1".to_string();
+        let mut departed_roster = roster(2, 0);
+        departed_roster.league_id = "1".to_string();
+        departed_roster.players = vec!["1234".to_string()];
+        db.upsert_users(&[departed_user]).unwrap();
+        db.upsert_rosters(
+            &serde_json::from_value(league.clone()).unwrap(),
+            &[departed_roster],
+        )
+        .unwrap();
+
+        let first = db.sync_league(&client, "1").await.unwrap();
+        assert_eq!(first.weeks, vec![1, 2]);
+        assert_eq!(first.transactions, 5);
+        assert_eq!(first.draft_picks, 1);
+        assert_eq!(db.last_synced_week("1").unwrap(), Some(2));
+
+        let second = db.sync_league(&client, "1").await.unwrap();
+        assert_eq!(second.weeks, vec![2]);
+        assert_eq!(second.transactions, 0);
+        assert_eq!(second.draft_picks, 0);
+
+        let counts: Vec<u32> = [
+            "leagues",
+            "users",
+            "rosters",
+            "roster_players",
+            "matchups",
+            "matchup_players",
+            "transactions",
+            "drafts",
+            "picks",
+        ]
+        .iter()
+        .map(|table| count(&db, table))
+        .collect();
+        assert_eq!(counts, vec![1, 1, 1, 3, 2, 4, 5, 1, 1]);
+
+        let (slot, team_name, pick_roster): (String, String, u8) = db
+            .connection()
+            .query_row(
+                "SELECT rp.slot, u.team_name, p.roster_id
+                 FROM roster_players rp
+                 JOIN rosters r USING (league_id, roster_id)
+                 JOIN users u ON u.league_id = r.league_id AND u.user_id = r.owner_id
+                 JOIN picks p ON p.player_id = rp.player_id
+                 WHERE rp.player_id = '4046'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            (slot.as_str(), team_name.as_str(), pick_roster),
+            ("QB", "Gridiron", 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_league_records_preseason_progress() {
+        let mut server = mockito::Server::new_async().await;
+        let mut league: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/fixtures/league.json")).unwrap();
+        league["league_id"] = "1".into();
+        league["settings"]["leg"] = 0.into();
+
+        let mocks = [
+            ("/league/1", league.to_string()),
+            ("/league/1/users", "[]".to_string()),
+            ("/league/1/rosters", "[]".to_string()),
+            ("/league/1/drafts", "[]".to_string()),
+        ];
+        for (path, body) in mocks {
+            server
+                .mock("GET", path)
+                .with_body(body)
+                .create_async()
+                .await;
+        }
+
+        let client = Client::with_base_url(&server.url());
+        let mut db = Database::open_in_memory().unwrap();
+        assert_eq!(db.last_synced_week("1").unwrap(), None);
+
+        let report = db.sync_league(&client, "1").await.unwrap();
+        assert!(report.weeks.is_empty());
+        assert_eq!(report.transactions, 0);
+        assert_eq!(db.last_synced_week("1").unwrap(), Some(0));
+    }
+
+    #[test]
+    fn test_upsert_players_twice() {
+        let players = AllPlayers::NFL(snapshot().players.into_iter().collect());
+        let mut db = Database::open_in_memory().unwrap();
+        db.upsert_players(&players).unwrap();
+        db.upsert_players(&players).unwrap();
+
+        assert_eq!(count(&db, "players"), 2);
+        let (birth_country, rotowire_id): (Option<String>, Option<u32>) = db
+            .connection()
+            .query_row(
+                "SELECT birth_country, rotowire_id FROM players WHERE player_id = '4046'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        let row = PlayerRow::from(&snapshot().players["4046"]);
+        assert_eq!(
+            (birth_country, rotowire_id),
+            (row.birth_country, row.rotowire_id)
+        );
+        assert!(matches!(
+            db.upsert_players(&AllPlayers::NBA(Default::default())),
+            Err(SleeperError::ExportError(_))
+        ));
+    }
+}
@@ -0,0 +1,327 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Tie,
+}
+
+impl Outcome {
+    /// The outcome for the side which scored `points_for`.
+    pub(crate) fn from_points(points_for: f64, points_against: f64) -> Outcome {
+        match points_for.partial_cmp(&points_against) {
+            Some(Ordering::Greater) => Outcome::Win,
+            Some(Ordering::Less) => Outcome::Loss,
+            _ => Outcome::Tie,
+        }
+    }
+}
+
+/// A win-loss-tie record.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Record {
+    pub wins: u16,
+    pub losses: u16,
+    pub ties: u16,
+}
+
+impl Record {
+    pub fn games(&self) -> u16 {
+        self.wins + self.losses + self.ties
+    }
+
+    /// Wins over games played, counting ties as half a win.
+    pub fn win_pct(&self) -> f64 {
+        match self.games() {
+            0 => 0.0,
+            games => (f64::from(self.wins) + f64::from(self.ties) / 2.0) / f64::from(games),
+        }
+    }
+
+    pub fn add(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Win => self.wins += 1,
+            Outcome::Loss => self.losses += 1,
+            Outcome::Tie => self.ties += 1,
+        }
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}-{}", self.wins, self.losses, self.ties)
+    }
+}
+
+/// Consecutive head-to-head results of the same kind, ending with the latest game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Streak {
+    pub outcome: Outcome,
+    pub length: u16,
+}
+
+impl fmt::Display for Streak {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.outcome {
+            Outcome::Win => "W",
+            Outcome::Loss => "L",
+            Outcome::Tie => "T",
+        };
+        write!(f, "{}{}", self.length, kind)
+    }
+}
+
+/// A single game from one roster's point of view.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct GameResult {
+    pub week: u8,
+    pub roster_id: u8,
+    /// `None` for games against the league median.
+    pub opponent: Option<u8>,
+    pub points_for: f64,
+    pub points_against: f64,
+    pub outcome: Outcome,
+}
+
+/// A roster's line in the league standings.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct StandingsRow {
+    /// 1-based overall rank.
+    pub rank: u8,
+    pub roster_id: u8,
+    pub owner_id: Option<OwnerId>,
+    pub division: Option<u8>,
+    /// 1-based rank within `division`.
+    pub division_rank: Option<u8>,
+    /// Overall record, including games against the median when the league plays them.
+    pub record: Record,
+    pub head_to_head_record: Record,
+    pub median_record: Record,
+    pub division_record: Record,
+    pub points_for: f64,
+    pub points_against: f64,
+    pub streak: Option<Streak>,
+}
+
+/// Whether `week` is a completed-or-in-progress regular season week for `league`.
+pub fn is_regular_season_week(league: &League, week: u8) -> bool {
+    let playoffs = league.settings.playoff_week_start;
+    week >= league.settings.start_week && (playoffs == 0 || week < playoffs)
+}
+
+/// Every regular season game in `matchups` (keyed by week), from both sides.
+/// Weeks in which nobody has scored yet are treated as unplayed and skipped.
+/// When `settings.league_average_match` is on, each roster also plays the
+/// week's median score.
+pub fn regular_season_games(
+    league: &League,
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+) -> Vec<GameResult> {
+    let mut games = Vec::new();
+
+    for (&week, week_matchups) in matchups {
+        if !is_regular_season_week(league, week)
+            || week_matchups.iter().all(|m| m.total_points() == 0.0)
+        {
+            continue;
+        }
+
+        let mut by_matchup: BTreeMap<u8, Vec<&Matchup>> = BTreeMap::new();
+        for matchup in week_matchups {
+            if let Some(id) = matchup.matchup_id {
+                by_matchup.entry(id).or_default().push(matchup);
+            }
+        }
+
+        for pair in by_matchup.values() {
+            if let [a, b] = pair[..] {
+                for (us, them) in [(a, b), (b, a)] {
+                    games.push(GameResult {
+                        week,
+                        roster_id: us.roster_id,
+                        opponent: Some(them.roster_id),
+                        points_for: us.total_points(),
+                        points_against: them.total_points(),
+                        outcome: Outcome::from_points(us.total_points(), them.total_points()),
+                    });
+                }
+            }
+        }
+
+        if league.settings.league_average_match == 1 {
+            let median = median(week_matchups.iter().map(Matchup::total_points));
+            for matchup in week_matchups {
+                games.push(GameResult {
+                    week,
+                    roster_id: matchup.roster_id,
+                    opponent: None,
+                    points_for: matchup.total_points(),
+                    points_against: median,
+                    outcome: Outcome::from_points(matchup.total_points(), median),
+                });
+            }
+        }
+    }
+
+    games
+}
+
+/// Rank every roster the way Sleeper does. Ties in win percentage are broken by
+/// total points for, and finally by `roster_id` so the order is always stable.
+pub fn standings(
+    league: &League,
+    rosters: &[Roster],
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+) -> Vec<StandingsRow> {
+    let games = regular_season_games(league, matchups);
+    let divisions: HashMap<u8, Option<u8>> = rosters
+        .iter()
+        .map(|r| (r.roster_id, r.settings.division))
+        .collect();
+
+    let mut rows: Vec<StandingsRow> = rosters
+        .iter()
+        .map(|roster| {
+            let mut row = StandingsRow {
+                rank: 0,
+                roster_id: roster.roster_id,
+                owner_id: roster.owner_id.clone(),
+                division: roster.settings.division,
+                division_rank: None,
+                record: Record::default(),
+                head_to_head_record: Record::default(),
+                median_record: Record::default(),
+                division_record: Record::default(),
+                points_for: 0.0,
+                points_against: 0.0,
+                streak: None,
+            };
+
+            for game in games.iter().filter(|g| g.roster_id == roster.roster_id) {
+                row.record.add(game.outcome);
+
+                let Some(opponent) = game.opponent else {
+                    row.median_record.add(game.outcome);
+                    continue;
+                };
+
+                row.head_to_head_record.add(game.outcome);
+                row.points_for += game.points_for;
+                row.points_against += game.points_against;
+
+                if row.division.is_some() && divisions.get(&opponent) == Some(&row.division) {
+                    row.division_record.add(game.outcome);
+                }
+
+                row.streak = match row.streak {
+                    Some(streak) if streak.outcome == game.outcome => Some(Streak {
+                        outcome: game.outcome,
+                        length: streak.length + 1,
+                    }),
+                    _ => Some(Streak {
+                        outcome: game.outcome,
+                        length: 1,
+                    }),
+                };
+            }
+
+            row.points_for = round_points(row.points_for);
+            row.points_against = round_points(row.points_against);
+            row
+        })
+        .collect();
+
+    rows.sort_by(|a, b| {
+        b.record
+            .win_pct()
+            .total_cmp(&a.record.win_pct())
+            .then_with(|| b.points_for.total_cmp(&a.points_for))
+            .then_with(|| a.roster_id.cmp(&b.roster_id))
+    });
+    for (i, row) in rows.iter_mut().enumerate() {
+        row.rank = i as u8 + 1;
+    }
+
+    let mut division_counts: HashMap<u8, u8> = HashMap::new();
+    for row in rows.iter_mut() {
+        if let Some(division) = row.division {
+            let count = division_counts.entry(division).or_default();
+            *count += 1;
+            row.division_rank = Some(*count);
+        }
+    }
+
+    rows
+}
+
+fn median(points: impl Iterator<Item = f64>) -> f64 {
+    let mut points: Vec<f64> = points.collect();
+    points.sort_by(f64::total_cmp);
+
+    match points.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => points[n / 2],
+        n => (points[n / 2 - 1] + points[n / 2]) / 2.0,
+    }
+}
+
+fn round_points(points: f64) -> f64 {
+    (points * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{league, rosters, season};
+
+    #[test]
+    fn test_standings_head_to_head_only() {
+        let rows = standings(&league(15, 0), &rosters(), &season());
+
+        let order: Vec<u8> = rows.iter().map(|r| r.roster_id).collect();
+        // 4 and 1 went 2-1 and 3 and 2 went 1-2, split by points for
+        assert_eq!(order, vec![4, 1, 3, 2]);
+
+        let first = &rows[0];
+        assert_eq!(first.rank, 1);
+        assert_eq!(first.record.to_string(), "2-1-0");
+        assert_eq!(first.points_for, 315.0);
+        assert_eq!(first.points_against, 305.0);
+        assert_eq!(first.streak.unwrap().to_string(), "2W");
+        assert_eq!(first.division_rank, Some(1));
+
+        let second = &rows[1];
+        assert_eq!(second.division_record.to_string(), "1-0-0");
+        assert_eq!(second.streak.unwrap().to_string(), "1L");
+
+        assert_eq!(rows[3].record.to_string(), "1-2-0");
+        assert_eq!(rows[3].division_rank, Some(2));
+    }
+
+    #[test]
+    fn test_standings_with_median_games() {
+        let rows = standings(&league(15, 1), &rosters(), &season());
+
+        let four = rows.iter().find(|r| r.roster_id == 4).unwrap();
+        assert_eq!(four.median_record.to_string(), "2-1-0");
+        assert_eq!(four.record.to_string(), "4-2-0");
+        assert_eq!(four.rank, 1);
+
+        let two = rows.iter().find(|r| r.roster_id == 2).unwrap();
+        assert_eq!(two.median_record.to_string(), "1-2-0");
+        assert_eq!(two.record.to_string(), "2-4-0");
+    }
+
+    #[test]
+    fn test_playoff_weeks_are_not_counted() {
+        let rows = standings(&league(3, 0), &rosters(), &season());
+        assert!(rows.iter().all(|r| r.record.games() == 2));
+    }
+}
@@ -0,0 +1,154 @@
+//! Fixtures shared by the unit tests of several modules.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::data::{AllPlayers, League, Matchup, NflPlayer, PlayerId, Roster, SleeperUser};
+use crate::snapshot::LeagueSnapshot;
+
+pub(crate) fn league(playoff_week_start: u8, league_average_match: u8) -> League {
+    let mut league: serde_json::Value =
+        serde_json::from_str(include_str!("../tests/fixtures/league.json")).unwrap();
+    league["settings"]["playoff_week_start"] = playoff_week_start.into();
+    league["settings"]["league_average_match"] = league_average_match.into();
+    serde_json::from_value(league).unwrap()
+}
+
+pub(crate) fn roster(roster_id: u8, division: u8) -> Roster {
+    serde_json::from_value(serde_json::json!({
+        "taxi": null,
+        "starters": [],
+        "settings": { "division": division },
+        "roster_id": roster_id,
+        "reserve": null,
+        "players": [],
+        "player_map": null,
+        "owner_id": format!("owner{}", roster_id),
+        "metadata": null,
+        "league_id": "999999999999999999",
+        "keepers": null,
+        "co_owners": null
+    }))
+    .unwrap()
+}
+
+pub(crate) fn matchup(roster_id: u8, matchup_id: u8, points: f64) -> Matchup {
+    serde_json::from_value(serde_json::json!({
+        "starters_points": [],
+        "starters": [],
+        "roster_id": roster_id,
+        "points": points,
+        "players_points": null,
+        "players": null,
+        "matchup_id": matchup_id,
+        "custom_points": null
+    }))
+    .unwrap()
+}
+
+/// Four rosters, two divisions (1 & 2 vs 3 & 4), three weeks played.
+pub(crate) fn season() -> BTreeMap<u8, Vec<Matchup>> {
+    [
+        (
+            1,
+            vec![
+                matchup(1, 1, 100.0),
+                matchup(2, 1, 90.0),
+                matchup(3, 2, 120.0),
+                matchup(4, 2, 80.0),
+            ],
+        ),
+        (
+            2,
+            vec![
+                matchup(1, 1, 110.0),
+                matchup(3, 1, 100.0),
+                matchup(2, 2, 95.0),
+                matchup(4, 2, 105.0),
+            ],
+        ),
+        (
+            3,
+            vec![
+                matchup(1, 1, 90.0),
+                matchup(4, 1, 130.0),
+                matchup(2, 2, 115.0),
+                matchup(3, 2, 100.0),
+            ],
+        ),
+        (
+            4,
+            vec![
+                matchup(1, 1, 0.0),
+                matchup(2, 1, 0.0),
+                matchup(3, 2, 0.0),
+                matchup(4, 2, 0.0),
+            ],
+        ),
+    ]
+    .into_iter()
+    .collect()
+}
+
+pub(crate) fn rosters() -> Vec<Roster> {
+    vec![roster(1, 1), roster(2, 1), roster(3, 2), roster(4, 2)]
+}
+
+pub(crate) fn user(user_id: &str, display_name: &str, team_name: Option<&str>) -> SleeperUser {
+    serde_json::from_value(serde_json::json!({
+        "user_id": user_id,
+        "username": display_name.to_lowercase(),
+        "settings": null,
+        "metadata": { "team_name": team_name },
+        "is_owner": false,
+        "is_bot": false,
+        "league_id": "999999999999999999",
+        "display_name": display_name,
+        "avatar": null
+    }))
+    .unwrap()
+}
+
+/// A matchup for `roster_id` with `(player_id, points, started)` entries.
+pub(crate) fn matchup_with_players(roster_id: u8, players: &[(&str, f64, bool)]) -> Matchup {
+    let points: f64 = players.iter().filter(|p| p.2).map(|p| p.1).sum();
+    serde_json::from_value(serde_json::json!({
+        "starters_points": players.iter().filter(|p| p.2).map(|p| p.1).collect::<Vec<f64>>(),
+        "starters": players.iter().filter(|p| p.2).map(|p| p.0).collect::<Vec<&str>>(),
+        "roster_id": roster_id,
+        "points": points,
+        "players_points": players.iter().map(|p| (p.0, p.1)).collect::<BTreeMap<&str, f64>>(),
+        "players": players.iter().map(|p| p.0).collect::<Vec<&str>>(),
+        "matchup_id": 1,
+        "custom_points": null
+    }))
+    .unwrap()
+}
+
+pub(crate) fn snapshot() -> LeagueSnapshot {
+    let mut rosters = vec![roster(1, 1), roster(2, 1)];
+    rosters[0].players = vec!["4046".to_string(), "KC".to_string()];
+    rosters[1].players = vec!["6794".to_string()];
+
+    let mut snapshot = LeagueSnapshot::new(
+        league(15, 0),
+        vec![
+            user("owner1", "Alice", Some("Gridiron")),
+            user("owner2", "Bob", None),
+        ],
+        rosters,
+        [(1, vec![matchup(1, 1, 100.0), matchup(2, 1, 90.0)])]
+            .into_iter()
+            .collect(),
+    );
+
+    let players: HashMap<PlayerId, NflPlayer> = serde_json::from_str(
+        r#"{
+            "4046": { "player_id": "4046", "first_name": "Patrick", "last_name": "Mahomes", "full_name": "Patrick Mahomes", "active": true, "sport": "nfl", "position": "QB", "team": "KC" },
+            "6794": { "player_id": "6794", "first_name": "Justin", "last_name": "Jefferson", "full_name": "Justin Jefferson", "active": true, "sport": "nfl", "position": "WR", "team": "MIN" },
+            "9999": { "player_id": "9999", "first_name": "Free", "last_name": "Agent", "full_name": "Free Agent", "active": false, "sport": "nfl" }
+        }"#,
+    )
+    .unwrap();
+    snapshot.attach_players(&AllPlayers::NFL(players));
+    snapshot
+}
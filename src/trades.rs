@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::*;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TradeConfig {
+    /// Only count the weeks a traded player was in the receiving roster's starting lineup.
+    pub started_only: bool,
+}
+
+/// How a completed trade has worked out so far.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TradeReport {
+    pub transaction_id: String,
+    pub week: u8,
+    pub created: Timestamp,
+    /// One side per roster involved, ordered by `roster_id`.
+    pub sides: Vec<TradeSide>,
+    /// The roster whose incoming players have scored the most. `None` while tied.
+    pub winner: Option<u8>,
+    /// How many points the winner is ahead of the next best side by.
+    pub margin: f64,
+}
+
+/// What one roster received in a trade.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TradeSide {
+    pub roster_id: u8,
+    pub players: Vec<PlayerProduction>,
+    pub draft_picks: Vec<TradedPick>,
+    pub faab_received: u32,
+    /// Total points of `players`.
+    pub points: f64,
+}
+
+/// Points an acquired player has scored for their new roster.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PlayerProduction {
+    pub player_id: PlayerId,
+    pub points: f64,
+    /// The weeks `points` were counted in.
+    pub weeks: Vec<u8>,
+}
+
+/// Report on every completed trade in `transactions`, oldest first.
+pub fn analyze_trades(
+    transactions: &[Transaction],
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+    config: &TradeConfig,
+) -> Vec<TradeReport> {
+    let mut trades: Vec<&Transaction> = transactions
+        .iter()
+        .filter(|t| t.transaction_type == TransactionType::Trade && t.is_complete())
+        .collect();
+    trades.sort_by_key(|t| t.created);
+
+    trades
+        .into_iter()
+        .map(|trade| analyze_trade(trade, matchups, config))
+        .collect()
+}
+
+/// Credit each player in `trade` to the roster which received them, from the
+/// week after the trade on, for as long as they stay on that roster. A player
+/// is on the roster in a given week when they appear in its matchup's `players`.
+pub fn analyze_trade(
+    trade: &Transaction,
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+    config: &TradeConfig,
+) -> TradeReport {
+    let mut roster_ids = trade.roster_ids.clone();
+    roster_ids.sort();
+    roster_ids.dedup();
+
+    let sides: Vec<TradeSide> = roster_ids
+        .into_iter()
+        .map(|roster_id| {
+            let players: Vec<PlayerProduction> = trade
+                .added_to(roster_id)
+                .into_iter()
+                .map(|player_id| {
+                    player_production(
+                        player_id,
+                        roster_id,
+                        trade.leg,
+                        matchups,
+                        config.started_only,
+                    )
+                })
+                .collect();
+
+            TradeSide {
+                roster_id,
+                points: round(players.iter().map(|p| p.points).sum()),
+                players,
+                draft_picks: trade
+                    .draft_picks
+                    .iter()
+                    .filter(|pick| pick.owner_id == roster_id)
+                    .cloned()
+                    .collect(),
+                faab_received: trade
+                    .waiver_budget
+                    .iter()
+                    .filter(|transfer| transfer.receiver == roster_id)
+                    .map(|transfer| transfer.amount)
+                    .sum(),
+            }
+        })
+        .collect();
+
+    let mut ranked: Vec<&TradeSide> = sides.iter().collect();
+    ranked.sort_by(|a, b| b.points.total_cmp(&a.points));
+    let (winner, margin) = match ranked[..] {
+        [first, second, ..] if first.points > second.points => {
+            (Some(first.roster_id), round(first.points - second.points))
+        }
+        _ => (None, 0.0),
+    };
+
+    TradeReport {
+        transaction_id: trade.transaction_id.clone(),
+        week: trade.leg,
+        created: trade.created,
+        sides,
+        winner,
+        margin,
+    }
+}
+
+/// Points `player_id` scored for `roster_id` in the weeks after `after_week`
+/// that they were on that roster (and, with `started_only`, in its starting
+/// lineup).
+///
+/// The week of the transaction itself is never counted: it may have been
+/// processed after that week's games, which were then played for the old roster.
+pub fn player_production(
+    player_id: &str,
+    roster_id: u8,
+    after_week: u8,
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+    started_only: bool,
+) -> PlayerProduction {
+    let mut points = 0.0;
+    let mut weeks = Vec::new();
+
+    for (&week, week_matchups) in matchups.range((Bound::Excluded(after_week), Bound::Unbounded)) {
+        let Some(matchup) = week_matchups.iter().find(|m| m.roster_id == roster_id) else {
+            continue;
+        };
+        if !matchup.has_player(player_id) || (started_only && !matchup.is_starter(player_id)) {
+            continue;
+        }
+
+        points += matchup.player_points(player_id).unwrap_or(0.0);
+        weeks.push(week);
+    }
+
+    PlayerProduction {
+        player_id: player_id.to_string(),
+        points: round(points),
+        weeks,
+    }
+}
+
+fn round(points: f64) -> f64 {
+    (points * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::matchup_with_players;
+
+    fn transactions() -> Vec<Transaction> {
+        serde_json::from_str(include_str!(
+            "../tests/fixtures/transactions_nfl_week_3.json"
+        ))
+        .unwrap()
+    }
+
+    fn matchups() -> BTreeMap<u8, Vec<Matchup>> {
+        [
+            (
+                2,
+                vec![
+                    matchup_with_players(1, &[("6794", 30.0, true), ("7564", 12.0, true)]),
+                    matchup_with_players(2, &[("4046", 40.0, true)]),
+                ],
+            ),
+            (
+                // The trade's week: the players already show up on their new
+                // rosters, but the games were played before the trade
+                3,
+                vec![
+                    matchup_with_players(1, &[("4046", 40.0, false)]),
+                    matchup_with_players(2, &[("6794", 30.0, false), ("7564", 12.0, false)]),
+                ],
+            ),
+            (
+                4,
+                vec![
+                    matchup_with_players(1, &[("4046", 25.0, true)]),
+                    matchup_with_players(2, &[("6794", 20.0, true), ("7564", 10.0, true)]),
+                ],
+            ),
+            (
+                5,
+                vec![
+                    matchup_with_players(1, &[("4046", 30.0, false)]),
+                    // 7564 has been dropped
+                    matchup_with_players(2, &[("6794", 18.0, true)]),
+                ],
+            ),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_trade_credits_points_after_the_trade() {
+        let reports = analyze_trades(&transactions(), &matchups(), &TradeConfig::default());
+        assert_eq!(reports.len(), 1);
+
+        let report = &reports[0];
+        assert_eq!(report.week, 3);
+        assert_eq!(report.sides[0].roster_id, 1);
+        assert_eq!(report.sides[0].points, 55.0);
+        assert_eq!(report.sides[0].players[0].weeks, vec![4, 5]);
+        assert_eq!(report.sides[0].faab_received, 10);
+        assert_eq!(report.sides[1].points, 48.0);
+        assert_eq!(report.sides[1].players[1].weeks, vec![4]);
+        assert_eq!(report.winner, Some(1));
+        assert_eq!(report.margin, 7.0);
+    }
+
+    #[test]
+    fn test_trade_started_only_and_picks() {
+        let config = TradeConfig { started_only: true };
+        let report = &analyze_trades(&transactions(), &matchups(), &config)[0];
+
+        assert_eq!(report.sides[0].points, 25.0);
+        assert_eq!(report.winner, Some(2));
+        assert_eq!(report.margin, 23.0);
+
+        assert_eq!(report.sides[0].draft_picks[0].round, 3);
+        assert_eq!(report.sides[1].draft_picks[0].round, 1);
+        assert_eq!(report.sides[1].draft_picks[0].roster_id, 1);
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::*;
+use crate::trades::{player_production, PlayerProduction};
+
+/// A single waiver claim and how it turned out.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct WaiverClaim {
+    pub transaction_id: String,
+    pub week: u8,
+    pub player_id: PlayerId,
+    pub dropped: Vec<PlayerId>,
+    pub bid: u32,
+    /// The highest other bid for the same player in the same week, if anybody else bid.
+    pub runner_up_bid: Option<u32>,
+    /// For winning bids, how much more than `runner_up_bid` was paid. The whole
+    /// bid of an uncontested claim counts as overpay.
+    pub overpay: Option<u32>,
+    /// Sleeper's reason for a failed claim.
+    pub notes: Option<String>,
+    /// What the player has scored for the roster since, for winning bids.
+    pub production: Option<PlayerProduction>,
+}
+
+/// One roster's waiver activity over the season.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RosterWaivers {
+    pub roster_id: u8,
+    pub budget: u32,
+    /// As Sleeper reports it in `RosterSettings::waiver_budget_used`.
+    pub spent: u32,
+    /// FAAB received from other rosters in completed trades.
+    pub received: u32,
+    /// FAAB sent to other rosters in completed trades.
+    pub sent: u32,
+    /// `budget` plus `received`, less `spent` and `sent`.
+    pub remaining: u32,
+    pub winning_bids: Vec<WaiverClaim>,
+    pub failed_bids: Vec<WaiverClaim>,
+    /// Mean `overpay` over winning bids. `None` without any.
+    pub average_overpay: Option<f64>,
+    /// Points scored since by every player won on waivers.
+    pub acquisition_points: f64,
+}
+
+/// FAAB spending and waiver results for every roster, ordered by `roster_id`.
+///
+/// Only waiver transactions count as bids; free agent pickups cost nothing.
+/// FAAB traded between rosters is applied to `remaining`. Points an
+/// acquisition produced are counted from the claim's week for as long as the
+/// player stayed on the roster.
+pub fn waiver_report(
+    league: &League,
+    rosters: &[Roster],
+    transactions: &[Transaction],
+    matchups: &BTreeMap<u8, Vec<Matchup>>,
+) -> Vec<RosterWaivers> {
+    let claims: Vec<(&Transaction, u8, &PlayerId, u32)> = transactions
+        .iter()
+        .filter(|t| t.transaction_type == TransactionType::Waiver)
+        .filter_map(|t| {
+            let (player_id, roster_id) = t.adds.as_ref()?.iter().next()?;
+            let bid = t.settings.as_ref().and_then(|s| s.waiver_bid).unwrap_or(0);
+            Some((t, *roster_id, player_id, bid))
+        })
+        .collect();
+
+    let mut reports: Vec<RosterWaivers> = rosters
+        .iter()
+        .map(|roster| {
+            let budget = league.settings.waiver_budget;
+            let spent = roster.settings.waiver_budget_used;
+            let transfers = transactions
+                .iter()
+                .filter(|t| t.is_complete())
+                .flat_map(|t| &t.waiver_budget);
+            let (mut received, mut sent) = (0, 0);
+            for transfer in transfers {
+                if transfer.receiver == roster.roster_id {
+                    received += transfer.amount;
+                }
+                if transfer.sender == roster.roster_id {
+                    sent += transfer.amount;
+                }
+            }
+            let mut winning_bids = Vec::new();
+            let mut failed_bids = Vec::new();
+
+            for (transaction, _, player_id, bid) in claims
+                .iter()
+                .filter(|(_, roster_id, _, _)| *roster_id == roster.roster_id)
+            {
+                let runner_up_bid = claims
+                    .iter()
+                    .filter(|(other, _, other_player, _)| {
+                        other.transaction_id != transaction.transaction_id
+                            && other.leg == transaction.leg
+                            && other_player == player_id
+                    })
+                    .map(|(_, _, _, bid)| *bid)
+                    .max();
+
+                let mut claim = WaiverClaim {
+                    transaction_id: transaction.transaction_id.clone(),
+                    week: transaction.leg,
+                    player_id: player_id.to_string(),
+                    dropped: transaction
+                        .dropped_by(roster.roster_id)
+                        .into_iter()
+                        .cloned()
+                        .collect(),
+                    bid: *bid,
+                    runner_up_bid,
+                    overpay: None,
+                    notes: transaction
+                        .metadata
+                        .as_ref()
+                        .and_then(|m| m.get("notes").cloned().flatten()),
+                    production: None,
+                };
+
+                match transaction.status {
+                    TransactionStatus::Complete => {
+                        claim.overpay = Some(bid.saturating_sub(runner_up_bid.unwrap_or(0)));
+                        // Waivers clear before the week's games, so unlike a
+                        // trade the claim's own week counts
+                        claim.production = Some(player_production(
+                            player_id,
+                            roster.roster_id,
+                            transaction.leg.saturating_sub(1),
+                            matchups,
+                            false,
+                        ));
+                        winning_bids.push(claim);
+                    }
+                    TransactionStatus::Failed => failed_bids.push(claim),
+                    _ => {}
+                }
+            }
+
+            let overpays: Vec<f64> = winning_bids
+                .iter()
+                .filter_map(|c| c.overpay)
+                .map(f64::from)
+                .collect();
+            let average_overpay = match overpays.len() {
+                0 => None,
+                n => Some(round(overpays.iter().sum::<f64>() / n as f64)),
+            };
+            let acquisition_points = winning_bids
+                .iter()
+                .filter_map(|c| c.production.as_ref())
+                .map(|p| p.points)
+                .sum();
+
+            RosterWaivers {
+                roster_id: roster.roster_id,
+                budget,
+                spent,
+                received,
+                sent,
+                remaining: (budget + received).saturating_sub(spent + sent),
+                winning_bids,
+                failed_bids,
+                average_overpay,
+                acquisition_points: round(acquisition_points),
+            }
+        })
+        .collect();
+
+    reports.sort_by_key(|r| r.roster_id);
+    reports
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{league, matchup_with_players, roster};
+
+    #[test]
+    fn test_waiver_report() {
+        let mut league = league(15, 0);
+        league.settings.waiver_type = 2;
+        assert!(league.settings.is_faab());
+
+        let mut rosters = vec![roster(3, 1), roster(1, 1), roster(2, 1)];
+        rosters[0].settings.waiver_budget_used = 25;
+        rosters[2].settings.waiver_budget_used = 5;
+
+        let transactions: Vec<Transaction> = serde_json::from_str(include_str!(
+            "../tests/fixtures/transactions_nfl_week_3.json"
+        ))
+        .unwrap();
+        let matchups: BTreeMap<u8, Vec<Matchup>> = [
+            (3, vec![matchup_with_players(3, &[("8110", 12.0, true)])]),
+            (
+                4,
+                vec![
+                    matchup_with_players(2, &[("9488", 6.5, true)]),
+                    matchup_with_players(3, &[("8110", 8.0, false)]),
+                ],
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let report = waiver_report(&league, &rosters, &transactions, &matchups);
+        let ids: Vec<u8> = report.iter().map(|r| r.roster_id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+
+        let (first, second, third) = (&report[0], &report[1], &report[2]);
+        assert!(first.winning_bids.is_empty());
+        assert_eq!(first.failed_bids[0].bid, 18);
+        assert_eq!(first.failed_bids[0].runner_up_bid, Some(25));
+        assert_eq!(
+            first.failed_bids[0].notes.as_deref(),
+            Some("This player was claimed by another owner.")
+        );
+        assert_eq!(first.average_overpay, None);
+        assert_eq!(first.received, 10);
+        assert_eq!(first.remaining, 110);
+
+        assert_eq!(second.winning_bids[0].dropped, vec!["1234"]);
+        assert_eq!(second.average_overpay, Some(5.0));
+        assert_eq!(second.acquisition_points, 6.5);
+        assert_eq!(second.sent, 10);
+        assert_eq!(second.remaining, 85);
+
+        assert_eq!(third.winning_bids[0].runner_up_bid, Some(18));
+        assert_eq!(third.average_overpay, Some(7.0));
+        assert_eq!(third.acquisition_points, 20.0);
+        assert_eq!(third.spent, 25);
+        assert_eq!(third.remaining, 75);
+    }
+}
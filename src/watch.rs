@@ -0,0 +1,544 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::time::Duration;
+
+#[cfg(feature = "watch")]
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "watch")]
+use tokio::time::{sleep, sleep_until, Instant};
+
+#[cfg(feature = "watch")]
+use crate::client::Client;
+use crate::data::*;
+
+/// Something that changed in a watched league between two polls.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum LeagueEvent {
+    ScoreChanged {
+        league_id: LeagueId,
+        week: u8,
+        roster_id: u8,
+        matchup_id: Option<u8>,
+        previous: f64,
+        points: f64,
+    },
+    TransactionCreated {
+        league_id: LeagueId,
+        transaction: Box<Transaction>,
+    },
+    RosterChanged {
+        league_id: LeagueId,
+        roster_id: u8,
+        added: Vec<PlayerId>,
+        removed: Vec<PlayerId>,
+    },
+    /// Raised once per roster the player is on.
+    InjuryStatusChanged {
+        league_id: LeagueId,
+        roster_id: u8,
+        player_id: PlayerId,
+        /// Whether the player is in the roster's current starting lineup.
+        starter: bool,
+        previous: InjuryStatus,
+        status: InjuryStatus,
+    },
+    /// The NFL week moved on. Not tied to any one league.
+    WeekAdvanced {
+        season: Season,
+        previous: u8,
+        week: u8,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct WatchConfig {
+    pub leagues: Vec<LeagueId>,
+    /// Time between polls of the leagues.
+    pub poll_interval: Duration,
+    /// Time between fetches of every NFL player, which injury statuses come
+    /// from. Sleeper asks that this large endpoint be called sparingly.
+    /// `None` turns off `InjuryStatusChanged` events.
+    pub players_interval: Option<Duration>,
+    /// Requests are spaced out to stay under this rate.
+    pub max_requests_per_minute: u32,
+}
+
+impl WatchConfig {
+    pub fn new(leagues: Vec<LeagueId>) -> Self {
+        WatchConfig {
+            leagues,
+            poll_interval: Duration::from_secs(60),
+            players_interval: Some(Duration::from_secs(6 * 60 * 60)),
+            // Sleeper's published limit is 1000 per minute; leave some headroom
+            max_requests_per_minute: 600,
+        }
+    }
+}
+
+/// What has been seen so far, which every new observation is compared with.
+/// The first observation of anything only records it, so a new watcher does
+/// not replay a league's whole history.
+#[derive(Clone, Debug, Default)]
+pub struct WatchState {
+    week: Option<u8>,
+    scores: HashMap<(LeagueId, u8, u8), f64>,
+    transactions: HashMap<LeagueId, HashSet<String>>,
+    rosters: HashMap<LeagueId, HashMap<u8, Roster>>,
+    injuries: HashMap<PlayerId, InjuryStatus>,
+}
+
+impl WatchState {
+    pub fn observe_sport_state(&mut self, state: &SportState) -> Option<LeagueEvent> {
+        let previous = self.week.replace(state.week)?;
+        (previous != state.week).then_some(LeagueEvent::WeekAdvanced {
+            season: state.season,
+            previous,
+            week: state.week,
+        })
+    }
+
+    pub fn observe_matchups(
+        &mut self,
+        league_id: &str,
+        week: u8,
+        matchups: &[Matchup],
+    ) -> Vec<LeagueEvent> {
+        let mut events = Vec::new();
+        for matchup in matchups {
+            let key = (league_id.to_string(), week, matchup.roster_id);
+            let points = matchup.total_points();
+            match self.scores.insert(key, points) {
+                Some(previous) if previous != points => events.push(LeagueEvent::ScoreChanged {
+                    league_id: league_id.to_string(),
+                    week,
+                    roster_id: matchup.roster_id,
+                    matchup_id: matchup.matchup_id,
+                    previous,
+                    points,
+                }),
+                _ => {}
+            }
+        }
+
+        events
+    }
+
+    pub fn observe_transactions(
+        &mut self,
+        league_id: &str,
+        transactions: &[Transaction],
+    ) -> Vec<LeagueEvent> {
+        let baseline = !self.transactions.contains_key(league_id);
+        let seen = self.transactions.entry(league_id.to_string()).or_default();
+
+        let mut created: Vec<&Transaction> = transactions
+            .iter()
+            .filter(|t| seen.insert(t.transaction_id.clone()))
+            .collect();
+        if baseline {
+            return Vec::new();
+        }
+
+        created.sort_by_key(|t| t.created);
+        created
+            .into_iter()
+            .map(|transaction| LeagueEvent::TransactionCreated {
+                league_id: league_id.to_string(),
+                transaction: Box::new(transaction.clone()),
+            })
+            .collect()
+    }
+
+    pub fn observe_rosters(&mut self, league_id: &str, rosters: &[Roster]) -> Vec<LeagueEvent> {
+        let known = self.rosters.entry(league_id.to_string()).or_default();
+
+        let mut events = Vec::new();
+        for roster in rosters {
+            if let Some(previous) = known.get(&roster.roster_id) {
+                let before: BTreeSet<&PlayerId> = previous.players.iter().collect();
+                let after: BTreeSet<&PlayerId> = roster.players.iter().collect();
+                if before != after {
+                    events.push(LeagueEvent::RosterChanged {
+                        league_id: league_id.to_string(),
+                        roster_id: roster.roster_id,
+                        added: after.difference(&before).map(|p| p.to_string()).collect(),
+                        removed: before.difference(&after).map(|p| p.to_string()).collect(),
+                    });
+                }
+            }
+            known.insert(roster.roster_id, roster.clone());
+        }
+
+        events
+    }
+
+    /// Compare injury statuses of every rostered player in the watched leagues,
+    /// as of the latest `observe_rosters`.
+    pub fn observe_players(&mut self, players: &HashMap<PlayerId, NflPlayer>) -> Vec<LeagueEvent> {
+        let mut changed: Vec<(&PlayerId, InjuryStatus, &InjuryStatus)> = Vec::new();
+        let rostered: BTreeSet<&PlayerId> = self
+            .rosters
+            .values()
+            .flat_map(|rosters| rosters.values())
+            .flat_map(|roster| &roster.players)
+            .collect();
+
+        for player_id in rostered {
+            let Some(player) = players.get(player_id) else {
+                continue;
+            };
+            match self
+                .injuries
+                .insert(player_id.clone(), player.injury_status.clone())
+            {
+                Some(previous) if previous != player.injury_status => {
+                    changed.push((player_id, previous, &player.injury_status))
+                }
+                _ => {}
+            }
+        }
+
+        let mut events = Vec::new();
+        for (player_id, previous, status) in changed {
+            for (league_id, rosters) in &self.rosters {
+                for roster in rosters.values().filter(|r| r.players.contains(player_id)) {
+                    events.push(LeagueEvent::InjuryStatusChanged {
+                        league_id: league_id.clone(),
+                        roster_id: roster.roster_id,
+                        player_id: player_id.clone(),
+                        starter: roster.starters.contains(player_id),
+                        previous: previous.clone(),
+                        status: status.clone(),
+                    });
+                }
+            }
+        }
+
+        events
+    }
+}
+
+/// Polls leagues for changes. Use `poll` to drive it by hand or `into_stream`
+/// to poll on `WatchConfig::poll_interval` forever. Needs the `watch` feature.
+#[cfg(feature = "watch")]
+pub struct Watcher {
+    client: Client,
+    config: WatchConfig,
+    state: WatchState,
+    next_request: Instant,
+    players_fetched: Option<Instant>,
+}
+
+#[cfg(feature = "watch")]
+impl Watcher {
+    pub fn new(client: Client, config: WatchConfig) -> Self {
+        Watcher {
+            client,
+            config,
+            state: WatchState::default(),
+            next_request: Instant::now(),
+            players_fetched: None,
+        }
+    }
+
+    /// Fetch everything once and return what changed since the last poll.
+    /// A failed request, or a response which could not be parsed, is reported
+    /// in place of that request's events; the rest of the poll still goes ahead.
+    pub async fn poll(&mut self) -> Vec<Result<LeagueEvent, SleeperError>> {
+        let mut events = Vec::new();
+
+        self.throttle().await;
+        let week = match self.client.get_sport_state(SleeperSport::NFL).await {
+            Ok(state) => {
+                events.extend(self.state.observe_sport_state(&state).map(Ok));
+                state.week
+            }
+            Err(e) => return vec![Err(e)],
+        };
+
+        for league_id in self.config.leagues.clone() {
+            self.throttle().await;
+            match self.client.get_rosters(&league_id).await {
+                Ok(rosters) => events.extend(
+                    self.state
+                        .observe_rosters(&league_id, &rosters)
+                        .into_iter()
+                        .map(Ok),
+                ),
+                Err(e) => events.push(Err(e)),
+            }
+
+            self.throttle().await;
+            match self.client.get_matchups(&league_id, week).await {
+                Ok(matchups) => events.extend(
+                    self.state
+                        .observe_matchups(&league_id, week, &matchups)
+                        .into_iter()
+                        .map(Ok),
+                ),
+                Err(e) => events.push(Err(e)),
+            }
+
+            self.throttle().await;
+            match self.client.get_transactions(&league_id, week).await {
+                Ok(transactions) => events.extend(
+                    self.state
+                        .observe_transactions(&league_id, &transactions)
+                        .into_iter()
+                        .map(Ok),
+                ),
+                Err(e) => events.push(Err(e)),
+            }
+        }
+
+        if self.players_due() {
+            self.throttle().await;
+            self.players_fetched = Some(Instant::now());
+            match self.client.get_all_players(SleeperSport::NFL).await {
+                Ok(AllPlayers::NFL(players)) => {
+                    events.extend(self.state.observe_players(&players).into_iter().map(Ok))
+                }
+                Ok(_) => {}
+                Err(e) => events.push(Err(e)),
+            }
+        }
+
+        events
+    }
+
+    /// Poll immediately, then every `poll_interval`, yielding events as they are found.
+    pub fn into_stream(self) -> impl Stream<Item = Result<LeagueEvent, SleeperError>> {
+        stream::unfold((self, true), |(mut watcher, first)| async move {
+            if !first {
+                sleep(watcher.config.poll_interval).await;
+            }
+            let events = watcher.poll().await;
+            Some((stream::iter(events), (watcher, false)))
+        })
+        .flatten()
+    }
+
+    fn players_due(&self) -> bool {
+        match (self.config.players_interval, self.players_fetched) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(interval), Some(fetched)) => fetched.elapsed() >= interval,
+        }
+    }
+
+    async fn throttle(&mut self) {
+        sleep_until(self.next_request).await;
+        let gap = Duration::from_secs(60) / self.config.max_requests_per_minute.max(1);
+        self.next_request = Instant::now() + gap;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{matchup, roster};
+
+    fn transaction(id: &str, created: u64) -> Transaction {
+        let mut transaction: Transaction = serde_json::from_str::<Vec<Transaction>>(include_str!(
+            "../tests/fixtures/transactions_nfl_week_3.json"
+        ))
+        .unwrap()
+        .remove(4);
+        transaction.transaction_id = id.to_string();
+        transaction.created = Timestamp(created);
+        transaction
+    }
+
+    fn player(id: &str, injury_status: &str) -> (PlayerId, NflPlayer) {
+        let player = serde_json::from_value(serde_json::json!({
+            "player_id": id,
+            "first_name": id,
+            "last_name": id,
+            "sport": "nfl",
+            "active": true,
+            "injury_status": injury_status
+        }))
+        .unwrap();
+        (id.to_string(), player)
+    }
+
+    #[test]
+    fn test_first_observation_is_a_baseline() {
+        let mut state = WatchState::default();
+        let mut roster = roster(1, 1);
+        roster.players = vec!["4046".to_string()];
+
+        assert!(state
+            .observe_matchups("1", 3, &[matchup(1, 1, 0.0)])
+            .is_empty());
+        assert!(state
+            .observe_transactions("1", &[transaction("a", 1)])
+            .is_empty());
+        assert!(state.observe_rosters("1", &[roster]).is_empty());
+        assert!(state
+            .observe_players(&[player("4046", "Questionable")].into_iter().collect())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_changes_are_reported_once() {
+        let mut state = WatchState::default();
+        let mut before = roster(1, 1);
+        before.players = vec!["4046".to_string(), "6794".to_string()];
+        before.starters = vec!["4046".to_string()];
+        state.observe_matchups("1", 3, &[matchup(1, 1, 0.0)]);
+        state.observe_transactions("1", &[transaction("a", 1)]);
+        state.observe_rosters("1", &[before.clone()]);
+        state.observe_players(&[player("4046", "Questionable")].into_iter().collect());
+
+        let scores = state.observe_matchups("1", 3, &[matchup(1, 1, 12.5)]);
+        assert_eq!(
+            scores,
+            vec![LeagueEvent::ScoreChanged {
+                league_id: "1".to_string(),
+                week: 3,
+                roster_id: 1,
+                matchup_id: Some(1),
+                previous: 0.0,
+                points: 12.5,
+            }]
+        );
+        assert!(state
+            .observe_matchups("1", 3, &[matchup(1, 1, 12.5)])
+            .is_empty());
+
+        let created = state.observe_transactions("1", &[transaction("a", 1), transaction("b", 2)]);
+        assert!(matches!(
+            &created[..],
+            [LeagueEvent::TransactionCreated { transaction, .. }] if transaction.transaction_id == "b"
+        ));
+        assert!(state
+            .observe_transactions("1", &[transaction("a", 1), transaction("b", 2)])
+            .is_empty());
+
+        let mut after = before.clone();
+        after.players = vec!["4046".to_string(), "5000".to_string()];
+        let changed = state.observe_rosters("1", &[after]);
+        assert!(matches!(
+            &changed[..],
+            [LeagueEvent::RosterChanged { added, removed, .. }] if added == &["5000"] && removed == &["6794"]
+        ));
+
+        let injuries = state.observe_players(&[player("4046", "Out")].into_iter().collect());
+        assert!(matches!(
+            &injuries[..],
+            [LeagueEvent::InjuryStatusChanged {
+                starter: true,
+                status: InjuryStatus::Out,
+                ..
+            }]
+        ));
+    }
+
+    #[cfg(feature = "watch")]
+    #[tokio::test]
+    async fn test_watcher_stream_reports_week_advancing() {
+        let mut server = mockito::Server::new_async().await;
+        let state_json = |week: u8| {
+            format!(
+                r#"{{"week": {0}, "season_type": "regular", "season_start_date": "2023-09-07",
+                    "season": "2023", "previous_season": "2022", "leg": {0},
+                    "league_season": "2023", "league_create_season": "2023", "display_week": {0}}}"#,
+                week
+            )
+        };
+        let matchups_json = |points: f64| {
+            format!(
+                r#"[{{"starters_points": [], "starters": [], "roster_id": 1, "points": {},
+                    "players_points": {{}}, "players": [], "matchup_id": 1, "custom_points": null}}]"#,
+                points
+            )
+        };
+
+        let first_state = server
+            .mock("GET", "/state/nfl")
+            .with_body(state_json(3))
+            .create_async()
+            .await;
+        let first_matchups = server
+            .mock("GET", "/league/1/matchups/3")
+            .with_body(matchups_json(0.0))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/league/1/rosters")
+            .with_body("[]")
+            .create_async()
+            .await;
+        server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex(r"^/league/1/transactions/\d+$".to_string()),
+            )
+            .with_body("[]")
+            .create_async()
+            .await;
+
+        let mut config = WatchConfig::new(vec!["1".to_string()]);
+        config.poll_interval = Duration::from_millis(10);
+        config.players_interval = None;
+        config.max_requests_per_minute = 60_000;
+        let mut watcher = Watcher::new(Client::with_base_url(&server.url()), config);
+
+        // The first poll is the baseline
+        assert!(watcher.poll().await.is_empty());
+        first_state.remove_async().await;
+        first_matchups.remove_async().await;
+        server
+            .mock("GET", "/state/nfl")
+            .with_body(state_json(4))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/league/1/matchups/4")
+            .with_body(matchups_json(0.0))
+            .create_async()
+            .await;
+
+        let mut stream = Box::pin(watcher.into_stream());
+        let event = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            event,
+            LeagueEvent::WeekAdvanced {
+                season: Season(2023),
+                previous: 3,
+                week: 4,
+            }
+        );
+    }
+
+    #[cfg(feature = "watch")]
+    #[tokio::test]
+    async fn test_watcher_reports_unparseable_players_as_an_error() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/state/nfl")
+            .with_body(
+                r#"{"week": 3, "season_type": "regular", "season_start_date": "2023-09-07",
+                    "season": "2023", "previous_season": "2022", "leg": 3,
+                    "league_season": "2023", "league_create_season": "2023", "display_week": 3}"#,
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/players/nfl")
+            .with_body(r#"{"4046": {"player_id": 4046}}"#)
+            .create_async()
+            .await;
+
+        let mut config = WatchConfig::new(Vec::new());
+        config.max_requests_per_minute = 60_000;
+        let mut watcher = Watcher::new(Client::with_base_url(&server.url()), config);
+
+        let events = watcher.poll().await;
+        assert!(matches!(
+            events[..],
+            [Err(SleeperError::DeserializationError(_))]
+        ));
+    }
+}